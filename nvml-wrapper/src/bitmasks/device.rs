@@ -73,6 +73,113 @@ bitflags! {
     }
 }
 
+impl ThrottleReasons {
+    /**
+    Gets a human-readable description of each reason in this bitmask, in the
+    order the flags are declared (e.g. `"Software thermal slowdown active"`).
+
+    The deprecated `USER_DEFINED_CLOCKS` flag is not described separately
+    from `APPLICATIONS_CLOCKS_SETTING`, and unknown bits are ignored. Returns an
+    empty `Vec` if there are no throttle reasons.
+    */
+    pub fn descriptions(&self) -> Vec<&'static str> {
+        const DESCRIPTIONS: [(ThrottleReasons, &str); 9] = [
+            (ThrottleReasons::GPU_IDLE, "GPU idle"),
+            (
+                ThrottleReasons::APPLICATIONS_CLOCKS_SETTING,
+                "Applications clocks setting",
+            ),
+            (ThrottleReasons::SW_POWER_CAP, "Software power cap active"),
+            (ThrottleReasons::HW_SLOWDOWN, "Hardware slowdown active"),
+            (ThrottleReasons::SYNC_BOOST, "Sync boost active"),
+            (
+                ThrottleReasons::SW_THERMAL_SLOWDOWN,
+                "Software thermal slowdown active",
+            ),
+            (
+                ThrottleReasons::HW_THERMAL_SLOWDOWN,
+                "Hardware thermal slowdown active",
+            ),
+            (
+                ThrottleReasons::HW_POWER_BRAKE_SLOWDOWN,
+                "Hardware power brake slowdown active",
+            ),
+            (
+                ThrottleReasons::DISPLAY_CLOCK_SETTING,
+                "Display clock setting",
+            ),
+        ];
+
+        DESCRIPTIONS
+            .iter()
+            .filter(|(flag, _)| self.contains(*flag))
+            .map(|&(_, description)| description)
+            .collect()
+    }
+}
+
+bitflags! {
+    /**
+    Flags used to specify why a GPU's clocks are being held below their maximum.
+
+    This is the newer name NVML uses for `ThrottleReasons`; the bits are the
+    same. NVML does not define `ClocksEventReason` names for the hardware
+    slowdown bits, so those flags use the `ClocksThrottleReason` constants.
+    */
+    // Checked against local
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    pub struct ClocksEventReasons: u64 {
+        /// Nothing is running on the GPU.
+        const GPU_IDLE                    = nvmlClocksEventReasonGpuIdle as u64;
+        /// GPU clocks are limited by the current applications clocks setting.
+        const APPLICATIONS_CLOCKS_SETTING = nvmlClocksEventReasonApplicationsClocksSetting as u64;
+        /// Software power scaling algorithm is reducing clocks.
+        const SW_POWER_CAP                = nvmlClocksEventReasonSwPowerCap as u64;
+        /// Hardware slowdown (reducing the core clocks by a factor of 2 or more)
+        /// is engaged.
+        const HW_SLOWDOWN                 = nvmlClocksThrottleReasonHwSlowdown as u64;
+        /// This GPU is being held at lower clocks by another GPU in its sync
+        /// boost group.
+        const SYNC_BOOST                  = nvmlClocksEventReasonSyncBoost as u64;
+        /// Software thermal slowdown.
+        const SW_THERMAL_SLOWDOWN         = nvmlClocksEventReasonSwThermalSlowdown as u64;
+        /// Hardware thermal slowdown is engaged, reducing core clocks by 2x or more.
+        const HW_THERMAL_SLOWDOWN         = nvmlClocksThrottleReasonHwThermalSlowdown as u64;
+        /// Hardware power brake slowdown is engaged, reducing core clocks by 2x or more.
+        const HW_POWER_BRAKE_SLOWDOWN     = nvmlClocksThrottleReasonHwPowerBrakeSlowdown as u64;
+        /// GPU clocks are limited by the current setting of display clocks.
+        const DISPLAY_CLOCK_SETTING       = nvmlClocksEventReasonDisplayClockSetting as u64;
+        /// Clocks are as high as possible.
+        const NONE                        = nvmlClocksEventReasonNone as u64;
+    }
+}
+
+impl ClocksEventReasons {
+    /**
+    Gets a human-readable description of each reason in this bitmask, in the
+    order the flags are declared (e.g. `"Software thermal slowdown active"`).
+
+    The bits are the same as `ThrottleReasons`', so this returns the same
+    descriptions as `ThrottleReasons::descriptions()`.
+    */
+    pub fn descriptions(&self) -> Vec<&'static str> {
+        ThrottleReasons::from(*self).descriptions()
+    }
+}
+
+impl From<ThrottleReasons> for ClocksEventReasons {
+    fn from(reasons: ThrottleReasons) -> Self {
+        Self::from_bits_retain(reasons.bits())
+    }
+}
+
+impl From<ClocksEventReasons> for ThrottleReasons {
+    fn from(reasons: ClocksEventReasons) -> Self {
+        Self::from_bits_retain(reasons.bits())
+    }
+}
+
 bitflags! {
     /// Flags that specify info about a frame capture session
     #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -88,3 +195,47 @@ bitflags! {
         const CAPTURE_WITH_WAIT_TIMEOUT   = NVML_NVFBC_SESSION_FLAG_CAPTURE_WITH_WAIT_TIMEOUT;
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{ClocksEventReasons, ThrottleReasons};
+
+    #[test]
+    fn throttle_reasons_descriptions() {
+        assert!(ThrottleReasons::NONE.descriptions().is_empty());
+        assert_eq!(ThrottleReasons::GPU_IDLE.descriptions(), vec!["GPU idle"]);
+        assert_eq!(
+            (ThrottleReasons::SW_POWER_CAP | ThrottleReasons::HW_THERMAL_SLOWDOWN).descriptions(),
+            vec![
+                "Software power cap active",
+                "Hardware thermal slowdown active"
+            ]
+        );
+        assert_eq!(
+            (ThrottleReasons::DISPLAY_CLOCK_SETTING | ThrottleReasons::APPLICATIONS_CLOCKS_SETTING)
+                .descriptions(),
+            vec!["Applications clocks setting", "Display clock setting"]
+        );
+    }
+
+    #[test]
+    fn clocks_event_reasons_descriptions() {
+        assert!(ClocksEventReasons::NONE.descriptions().is_empty());
+        assert_eq!(
+            ClocksEventReasons::SYNC_BOOST.descriptions(),
+            vec!["Sync boost active"]
+        );
+        assert_eq!(
+            (ClocksEventReasons::SW_THERMAL_SLOWDOWN | ClocksEventReasons::GPU_IDLE).descriptions(),
+            vec!["GPU idle", "Software thermal slowdown active"]
+        );
+        assert_eq!(
+            ClocksEventReasons::from(ThrottleReasons::HW_SLOWDOWN).descriptions(),
+            vec!["Hardware slowdown active"]
+        );
+        assert_eq!(
+            ThrottleReasons::from(ClocksEventReasons::DISPLAY_CLOCK_SETTING),
+            ThrottleReasons::DISPLAY_CLOCK_SETTING
+        );
+    }
+}
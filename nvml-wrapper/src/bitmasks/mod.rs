@@ -1,6 +1,7 @@
 pub mod device;
 pub mod event;
 pub mod nv_link;
+pub mod vgpu;
 
 use crate::ffi::bindings::*;
 use bitflags::bitflags;
@@ -28,3 +29,11 @@ bitflags! {
         const NO_ATTACH = NVML_INIT_FLAG_NO_ATTACH;
     }
 }
+
+impl InitFlags {
+    /// `NO_GPUS | NO_ATTACH`; initialize NVML without requiring any GPUs to be
+    /// present and without attaching to them.
+    pub const fn all_safe() -> Self {
+        Self::NO_GPUS.union(Self::NO_ATTACH)
+    }
+}
@@ -0,0 +1,44 @@
+use crate::ffi::bindings::*;
+use bitflags::bitflags;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+
+bitflags! {
+    /// The ways in which a VM running a vGPU can be migrated to a physical GPU.
+    ///
+    /// Returned as part of `Nvml.vgpu_compatibility()`.
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    pub struct VgpuVmCompatibility: u32 {
+        /// The vGPU is not runnable on the physical GPU.
+        const NONE      = nvmlVgpuVmCompatibility_enum_NVML_VGPU_VM_COMPATIBILITY_NONE;
+        /// The vGPU is runnable from a cold / powered-off state (ACPI S5).
+        const COLD      = nvmlVgpuVmCompatibility_enum_NVML_VGPU_VM_COMPATIBILITY_COLD;
+        /// The vGPU is runnable from a hibernated state (ACPI S4).
+        const HIBERNATE = nvmlVgpuVmCompatibility_enum_NVML_VGPU_VM_COMPATIBILITY_HIBERNATE;
+        /// The vGPU is runnable from a sleeped state (ACPI S3).
+        const SLEEP     = nvmlVgpuVmCompatibility_enum_NVML_VGPU_VM_COMPATIBILITY_SLEEP;
+        /// The vGPU is runnable from a live / paused (ACPI S0) state.
+        const LIVE      = nvmlVgpuVmCompatibility_enum_NVML_VGPU_VM_COMPATIBILITY_LIVE;
+    }
+}
+
+bitflags! {
+    /// What limits the compatibility of a vGPU with a physical GPU.
+    ///
+    /// Returned as part of `Nvml.vgpu_compatibility()`.
+    #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+    #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+    pub struct VgpuCompatibilityLimit: u32 {
+        /// Compatibility is not limited.
+        const NONE         = nvmlVgpuPgpuCompatibilityLimitCode_enum_NVML_VGPU_COMPATIBILITY_LIMIT_NONE;
+        /// Compatibility is limited by the host driver version.
+        const HOST_DRIVER  = nvmlVgpuPgpuCompatibilityLimitCode_enum_NVML_VGPU_COMPATIBILITY_LIMIT_HOST_DRIVER;
+        /// Compatibility is limited by the guest driver version.
+        const GUEST_DRIVER = nvmlVgpuPgpuCompatibilityLimitCode_enum_NVML_VGPU_COMPATIBILITY_LIMIT_GUEST_DRIVER;
+        /// Compatibility is limited by the GPU hardware.
+        const GPU          = nvmlVgpuPgpuCompatibilityLimitCode_enum_NVML_VGPU_COMPATIBILITY_LIMIT_GPU;
+        /// Compatibility is limited by an undefined factor.
+        const OTHER        = nvmlVgpuPgpuCompatibilityLimitCode_enum_NVML_VGPU_COMPATIBILITY_LIMIT_OTHER;
+    }
+}
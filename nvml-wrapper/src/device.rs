@@ -3,38 +3,52 @@ use crate::EventSet;
 use crate::NvLink;
 use crate::Nvml;
 
-use crate::bitmasks::device::ThrottleReasons;
+use crate::bitmasks::device::{ClocksEventReasons, ThrottleReasons};
 #[cfg(target_os = "linux")]
 use crate::bitmasks::event::EventTypes;
 #[cfg(target_os = "windows")]
 use crate::bitmasks::Behavior;
 
+use crate::enum_wrappers::nv_link::{ErrorCounter, RemoteDeviceType};
 use crate::enum_wrappers::{bool_from_state, device::*, state_from_bool};
 
 use crate::enums::device::BusType;
 use crate::enums::device::DeviceArchitecture;
 use crate::enums::device::GpuLockedClocksSetting;
-use crate::enums::device::PcieLinkMaxSpeed;
 use crate::enums::device::PowerSource;
+use crate::enums::device::SampleValue;
+use crate::enums::device::UsedGpuMemory;
+use crate::enums::device::{PcieGeneration, PcieLinkMaxSpeed};
 #[cfg(target_os = "linux")]
 use crate::error::NvmlErrorWithSource;
+#[cfg(target_os = "linux")]
+use crate::error::RegisterEventsError;
 use crate::error::{nvml_sym, nvml_try, Bits, NvmlError};
 
+use crate::ffi::bindings::field_id::*;
 use crate::ffi::bindings::*;
 
+use crate::high_level::CachedDevice;
+
 use crate::struct_wrappers::device::*;
+use crate::struct_wrappers::vgpu::VgpuPgpuMetadata;
 use crate::structs::device::*;
+use crate::structs::nv_link::NvLinkSummary;
+use crate::vgpu::{metadata_buffer, VgpuInstance, VgpuTypeId};
 
 #[cfg(target_os = "linux")]
 use std::convert::TryInto;
 #[cfg(target_os = "linux")]
 use std::os::raw::c_ulong;
 use std::{
+    collections::{HashMap, HashSet},
     convert::TryFrom,
     ffi::CStr,
+    hash::{Hash, Hasher},
     mem,
     os::raw::{c_int, c_uint, c_ulonglong},
-    ptr,
+    ptr, thread,
+    time::Duration,
 };
 
 use static_assertions::assert_impl_all;
@@ -66,6 +80,12 @@ device.fan_speed(0)?;
 
 This means you shouldn't have to worry about calls to `Device` methods returning
 `Uninitialized` errors.
+
+Two `Device`s are equal (and hash the same) if they wrap the same underlying
+handle, which means they refer to the same GPU. NVML only guarantees that a
+handle is stable for as long as it stays initialized, so don't compare `Device`s
+obtained from different `Nvml` instances; use `.uuid()` if you need an
+identifier that is stable across sessions.
 */
 #[derive(Debug)]
 pub struct Device<'nvml> {
@@ -78,6 +98,20 @@ unsafe impl<'nvml> Sync for Device<'nvml> {}
 
 assert_impl_all!(Device: Send, Sync);
 
+impl<'nvml> PartialEq for Device<'nvml> {
+    fn eq(&self, other: &Self) -> bool {
+        self.device == other.device
+    }
+}
+
+impl<'nvml> Eq for Device<'nvml> {}
+
+impl<'nvml> Hash for Device<'nvml> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.device.hash(state);
+    }
+}
+
 impl<'nvml> Device<'nvml> {
     /**
     Create a new `Device` wrapper.
@@ -112,6 +146,32 @@ impl<'nvml> Device<'nvml> {
         self.device
     }
 
+    /**
+    Create another `Device` that wraps the same handle and `Nvml` reference as
+    this one.
+
+    Both `Device`s refer to the same GPU and compare equal; neither owns the
+    GPU or the handle, so dropping one has no effect on the other. This is
+    cheaper than looking the `Device` up again by index or UUID when it needs
+    to be handed to several components.
+    */
+    pub fn duplicate(&self) -> Device<'nvml> {
+        Self {
+            device: self.device,
+            nvml: self.nvml,
+        }
+    }
+
+    /// Wrap this `Device` in a `CachedDevice`, which only calls into NVML the
+    /// first time each piece of immutable metadata (such as the name or UUID)
+    /// is read.
+    ///
+    /// See the `high_level::cached_device` module docs for which fields are
+    /// cached.
+    pub fn cached(&self) -> CachedDevice<'_, 'nvml> {
+        CachedDevice::new(self)
+    }
+
     /**
     Clear all affinity bindings for the calling thread.
 
@@ -216,6 +276,18 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Same as `.applications_clock()`, but returns the clock as a `Frequency`.
+
+    # Errors
+
+    See `.applications_clock()`.
+    */
+    // Tested
+    pub fn applications_clock_typed(&self, clock_type: Clock) -> Result<Frequency, NvmlError> {
+        self.applications_clock(clock_type).map(Frequency)
+    }
+
     /**
     Gets the current and default state of auto boosted clocks.
 
@@ -334,12 +406,14 @@ impl<'nvml> Device<'nvml> {
 
     See the `Brand` enum for documentation of possible values.
 
+    Brand values introduced by drivers newer than this wrapper are reported as
+    `Brand::Unknown` rather than as an error.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
     * `InvalidArg`, if this `Device` is invalid
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
-    * `UnexpectedVariant`, check that error's docs for more info
     * `Unknown`, on any unexpected error
     */
     // Checked against local nvml.h
@@ -352,7 +426,10 @@ impl<'nvml> Device<'nvml> {
             let mut brand: nvmlBrandType_t = mem::zeroed();
             nvml_try(sym(self.device, &mut brand))?;
 
-            Brand::try_from(brand)
+            match Brand::try_from(brand) {
+                Err(NvmlError::UnexpectedVariant(_)) => Ok(Brand::Unknown),
+                other => other,
+            }
         }
     }
 
@@ -367,7 +444,6 @@ impl<'nvml> Device<'nvml> {
     * `InvalidArg`, if this `Device` is invalid
     * `NotSupported`, if this `Device` does not support this feature
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
-    * `UnexpectedVariant`, for which you can read the docs for
     * `Unknown`, on any unexpected error
 
     # Device Support
@@ -384,7 +460,7 @@ impl<'nvml> Device<'nvml> {
             let mut info: nvmlBridgeChipHierarchy_t = mem::zeroed();
             nvml_try(sym(self.device, &mut info))?;
 
-            BridgeChipHierarchy::try_from(info)
+            Ok(BridgeChipHierarchy::from(info))
         }
     }
 
@@ -423,6 +499,51 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Same as `.clock()`, but returns the clock as a `Frequency`.
+
+    # Errors
+
+    See `.clock()`.
+    */
+    // Tested
+    pub fn clock_typed(
+        &self,
+        clock_type: Clock,
+        clock_id: ClockId,
+    ) -> Result<Frequency, NvmlError> {
+        self.clock(clock_type, clock_id).map(Frequency)
+    }
+
+    /**
+    Gets every clock speed that NVML reports for the given `Clock` type, in MHz.
+
+    This calls `.clock()` for each `ClockId` and `.max_clock_info()`. Entries
+    that this `Device` does not support are `None` in the returned `ClockTable`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid or `clock_type` is invalid (shouldn't occur?)
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    pub fn clock_table(&self, clock_type: Clock) -> Result<ClockTable, NvmlError> {
+        Ok(ClockTable {
+            current: optional(self.clock(clock_type.clone(), ClockId::Current))?,
+            target_app_clock: optional(self.clock(clock_type.clone(), ClockId::TargetAppClock))?,
+            default_app_clock: optional(self.clock(clock_type.clone(), ClockId::DefaultAppClock))?,
+            customer_max_boost: optional(
+                self.clock(clock_type.clone(), ClockId::CustomerMaxBoost),
+            )?,
+            max: optional(self.max_clock_info(clock_type))?,
+        })
+    }
+
     /**
     Gets this `Device`'s customer-defined maximum boost clock speed for the
     given `Clock` type.
@@ -455,6 +576,21 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Same as `.max_customer_boost_clock()`, but returns the clock as a `Frequency`.
+
+    # Errors
+
+    See `.max_customer_boost_clock()`.
+    */
+    // Tested
+    pub fn max_customer_boost_clock_typed(
+        &self,
+        clock_type: Clock,
+    ) -> Result<Frequency, NvmlError> {
+        self.max_customer_boost_clock(clock_type).map(Frequency)
+    }
+
     /**
     Gets the current compute mode for this `Device`.
 
@@ -538,6 +674,18 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Same as `.clock_info()`, but returns the clock as a `Frequency`.
+
+    # Errors
+
+    See `.clock_info()`.
+    */
+    // Tested
+    pub fn clock_info_typed(&self, clock_type: Clock) -> Result<Frequency, NvmlError> {
+        self.clock_info(clock_type).map(Frequency)
+    }
+
     /**
     Gets information about processes with a compute context running on this `Device`.
 
@@ -545,6 +693,9 @@ impl<'nvml> Device<'nvml> {
     with an active context). Graphics applications (OpenGL, DirectX) won't be listed by this
     function.
 
+    Returns an empty `Vec` if there are no such processes, including on
+    drivers that report that case as `NotFound`.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
@@ -573,7 +724,11 @@ impl<'nvml> Device<'nvml> {
             count += 5;
             let mut processes: Vec<nvmlProcessInfo_t> = vec![mem::zeroed(); count as usize];
 
-            nvml_try(sym(self.device, &mut count, processes.as_mut_ptr()))?;
+            match nvml_try(sym(self.device, &mut count, processes.as_mut_ptr())) {
+                // Some drivers report no processes as `NotFound`
+                Err(NvmlError::NotFound) => return Ok(vec![]),
+                other => other?,
+            }
 
             processes.truncate(count as usize);
             Ok(processes.into_iter().map(ProcessInfo::from).collect())
@@ -587,6 +742,9 @@ impl<'nvml> Device<'nvml> {
     with an active context). Graphics applications (OpenGL, DirectX) won't be counted by this
     function.
 
+    Returns 0 if there are no such processes, including on drivers that
+    report that case as `NotFound`.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
@@ -611,6 +769,8 @@ impl<'nvml> Device<'nvml> {
             // Passing null doesn't mean we want the count, it's just allowed
             match sym(self.device, &mut count, ptr::null_mut()) {
                 nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => Ok(count),
+                // Some drivers report no processes as `NotFound`
+                nvmlReturn_enum_NVML_ERROR_NOT_FOUND => Ok(0),
                 // If success, return 0; otherwise, return error
                 other => nvml_try(other).map(|_| 0),
             }
@@ -624,6 +784,9 @@ impl<'nvml> Device<'nvml> {
     with an active context). Graphics applications (OpenGL, DirectX) won't be listed by this
     function.
 
+    Returns an empty `Vec` if there are no such processes, including on
+    drivers that report that case as `NotFound`.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
@@ -652,7 +815,11 @@ impl<'nvml> Device<'nvml> {
             count += 5;
             let mut processes: Vec<nvmlProcessInfo_v2_t> = vec![mem::zeroed(); count as usize];
 
-            nvml_try(sym(self.device, &mut count, processes.as_mut_ptr()))?;
+            match nvml_try(sym(self.device, &mut count, processes.as_mut_ptr())) {
+                // Some drivers report no processes as `NotFound`
+                Err(NvmlError::NotFound) => return Ok(vec![]),
+                other => other?,
+            }
 
             processes.truncate(count as usize);
             Ok(processes.into_iter().map(ProcessInfo::from).collect())
@@ -666,6 +833,9 @@ impl<'nvml> Device<'nvml> {
     with an active context). Graphics applications (OpenGL, DirectX) won't be counted by this
     function.
 
+    Returns 0 if there are no such processes, including on drivers that
+    report that case as `NotFound`.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
@@ -690,6 +860,8 @@ impl<'nvml> Device<'nvml> {
             // Passing null doesn't mean we want the count, it's just allowed
             match sym(self.device, &mut count, ptr::null_mut()) {
                 nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => Ok(count),
+                // Some drivers report no processes as `NotFound`
+                nvmlReturn_enum_NVML_ERROR_NOT_FOUND => Ok(0),
                 // If success, return 0; otherwise, return error
                 other => nvml_try(other).map(|_| 0),
             }
@@ -802,6 +974,36 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets the usable bandwidth of this `Device`'s current PCIe link in one
+    direction, in Gbit/s.
+
+    This is the per-lane transfer rate of the current link generation, less
+    line encoding overhead, multiplied by the current link width. See
+    `PcieGeneration` for the per-generation figures. Protocol overhead is not
+    accounted for, so real-world throughput will be somewhat lower.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if PCIe link information is not available
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, if the link generation is not one this crate knows about
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Fermi or newer fully supported devices.
+    */
+    // Tested
+    pub fn pcie_bandwidth_gbps(&self) -> Result<f64, NvmlError> {
+        let gen = PcieGeneration::try_from(self.current_pcie_link_gen()?)?;
+        let width = self.current_pcie_link_width()?;
+
+        Ok(gen.lane_bandwidth_gbps() * width as f64)
+    }
+
     /**
     Gets the current utilization and sampling size (sampling size in μs) for the Decoder.
 
@@ -836,6 +1038,103 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets the current utilization and sampling size (sampling size in μs) for the JPEG engine.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Turing or newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetJpgUtilization")]
+    pub fn jpeg_utilization(&self) -> Result<UtilizationInfo, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetJpgUtilization.as_ref())?;
+
+        unsafe {
+            let mut utilization: c_uint = mem::zeroed();
+            let mut sampling_period: c_uint = mem::zeroed();
+
+            nvml_try(sym(self.device, &mut utilization, &mut sampling_period))?;
+
+            Ok(UtilizationInfo {
+                utilization,
+                sampling_period,
+            })
+        }
+    }
+
+    /**
+    Gets the current utilization and sampling size (sampling size in μs) for the Optical Flow Accelerator (OFA).
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Turing or newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetOfaUtilization")]
+    pub fn ofa_utilization(&self) -> Result<UtilizationInfo, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetOfaUtilization.as_ref())?;
+
+        unsafe {
+            let mut utilization: c_uint = mem::zeroed();
+            let mut sampling_period: c_uint = mem::zeroed();
+
+            nvml_try(sym(self.device, &mut utilization, &mut sampling_period))?;
+
+            Ok(UtilizationInfo {
+                utilization,
+                sampling_period,
+            })
+        }
+    }
+
+    /**
+    Gets the utilization of each of this `Device`'s media engines (encoder,
+    decoder, JPEG and OFA) in one call.
+
+    Engines that this `Device` does not have or that the loaded driver cannot
+    report on are `None` in the returned `EngineUtilization`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler or newer fully supported devices.
+    */
+    pub fn all_engine_utilization(&self) -> Result<EngineUtilization, NvmlError> {
+        let optional = |result: Result<UtilizationInfo, NvmlError>| match result {
+            Ok(info) => Ok(Some(info)),
+            Err(NvmlError::NotSupported) | Err(NvmlError::FunctionNotFound) => Ok(None),
+            Err(e) => Err(e),
+        };
+
+        Ok(EngineUtilization {
+            encoder: optional(self.encoder_utilization())?,
+            decoder: optional(self.decoder_utilization())?,
+            jpeg: optional(self.jpeg_utilization())?,
+            ofa: optional(self.ofa_utilization())?,
+        })
+    }
+
     /**
     Gets global statistics for active frame buffer capture sessions on this `Device`.
 
@@ -957,6 +1256,21 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Same as `.default_applications_clock()`, but returns the clock as a `Frequency`.
+
+    # Errors
+
+    See `.default_applications_clock()`.
+    */
+    // Tested
+    pub fn default_applications_clock_typed(
+        &self,
+        clock_type: Clock,
+    ) -> Result<Frequency, NvmlError> {
+        self.default_applications_clock(clock_type).map(Frequency)
+    }
+
     /// Not documenting this because it's deprecated. Read NVIDIA's docs if you
     /// must use it.
     #[deprecated(note = "use `Device.memory_error_counter()`")]
@@ -982,6 +1296,89 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets ECC error counts for this `Device`, broken down by memory location.
+
+    This is a replacement for the deprecated `.detailed_ecc_errors()` built on
+    the per-location `NVML_FI_DEV_ECC_*` field values, which are all read in a
+    single `.field_values_for()` call. `MemoryError::Corrected` selects the
+    single bit error counts and `MemoryError::Uncorrected` the double bit error
+    counts.
+
+    Locations that this `Device` does not report a count for (e.g. texture
+    memory on newer architectures) are `None` in the returned
+    `DetailedEccErrors`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support field values
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Fermi and newer fully supported devices with ECC memory.
+    */
+    // Tested
+    pub fn detailed_ecc_errors_modern(
+        &self,
+        error_type: MemoryError,
+        counter_type: EccCounter,
+    ) -> Result<DetailedEccErrors, NvmlError> {
+        let ids = match (error_type, counter_type) {
+            (MemoryError::Corrected, EccCounter::Volatile) => [
+                NVML_FI_DEV_ECC_SBE_VOL_L1,
+                NVML_FI_DEV_ECC_SBE_VOL_L2,
+                NVML_FI_DEV_ECC_SBE_VOL_DEV,
+                NVML_FI_DEV_ECC_SBE_VOL_REG,
+                NVML_FI_DEV_ECC_SBE_VOL_TEX,
+            ],
+            (MemoryError::Corrected, EccCounter::Aggregate) => [
+                NVML_FI_DEV_ECC_SBE_AGG_L1,
+                NVML_FI_DEV_ECC_SBE_AGG_L2,
+                NVML_FI_DEV_ECC_SBE_AGG_DEV,
+                NVML_FI_DEV_ECC_SBE_AGG_REG,
+                NVML_FI_DEV_ECC_SBE_AGG_TEX,
+            ],
+            (MemoryError::Uncorrected, EccCounter::Volatile) => [
+                NVML_FI_DEV_ECC_DBE_VOL_L1,
+                NVML_FI_DEV_ECC_DBE_VOL_L2,
+                NVML_FI_DEV_ECC_DBE_VOL_DEV,
+                NVML_FI_DEV_ECC_DBE_VOL_REG,
+                NVML_FI_DEV_ECC_DBE_VOL_TEX,
+            ],
+            (MemoryError::Uncorrected, EccCounter::Aggregate) => [
+                NVML_FI_DEV_ECC_DBE_AGG_L1,
+                NVML_FI_DEV_ECC_DBE_AGG_L2,
+                NVML_FI_DEV_ECC_DBE_AGG_DEV,
+                NVML_FI_DEV_ECC_DBE_AGG_REG,
+                NVML_FI_DEV_ECC_DBE_AGG_TEX,
+            ],
+        };
+
+        let mut counts = self
+            .field_values_for(&ids.map(FieldId))?
+            .into_iter()
+            .map(|sample| match sample.and_then(|s| s.value) {
+                Ok(SampleValue::U32(v)) => Some(v as u64),
+                Ok(SampleValue::U64(v)) => Some(v),
+                Ok(SampleValue::I64(v)) => u64::try_from(v).ok(),
+                _ => None,
+            });
+
+        let mut next = || counts.next().flatten();
+
+        Ok(DetailedEccErrors {
+            l1_cache: next(),
+            l2_cache: next(),
+            device_memory: next(),
+            register_file: next(),
+            texture_memory: next(),
+        })
+    }
+
     /**
     Gets the display active state for this `Device`.
 
@@ -1043,6 +1440,29 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets both the display active state and the display connected state for this
+    `Device`.
+
+    See `.is_display_active()` and `.is_display_connected()` for what each of
+    these means. NVML does not expose the number of connected displays.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+    */
+    pub fn display_status(&self) -> Result<DisplayStatus, NvmlError> {
+        Ok(DisplayStatus {
+            is_active: self.is_display_active()?,
+            is_connected: self.is_display_connected()?,
+        })
+    }
+
     /**
     Gets the current and pending driver model for this `Device`.
 
@@ -1088,46 +1508,49 @@ impl<'nvml> Device<'nvml> {
     }
 
     /**
-    Get the current and pending ECC modes for this `Device`.
+    Gets the current and pending driver model for this `Device`, or `None` on
+    platforms that don't have driver models.
 
-    Changing ECC modes requires a reboot. The "pending" ECC mode refers to the target
-    mode following the next reboot.
+    This lets cross-platform code ask for the driver model without a
+    `#[cfg(target_os = "windows")]` at every call site. On Windows this calls
+    `.driver_model()` and also returns `None` if this `Device` doesn't support
+    the query; everywhere else it always returns `Ok(None)`.
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
     * `InvalidArg`, if this `Device` is invalid
-    * `NotSupported`, if this `Device` does not support this feature
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
     * `UnexpectedVariant`, for which you can read the docs for
     * `Unknown`, on any unexpected error
 
     # Device Support
 
-    Supports Fermi and newer fully supported devices. Only applicable to devices with
-    ECC. Requires `InfoRom::ECC` version 1.0 or higher.
+    Supports Fermi and newer fully supported devices.
     */
-    // Checked against local
-    // Tested on machines other than my own
-    #[doc(alias = "nvmlDeviceGetEccMode")]
-    pub fn is_ecc_enabled(&self) -> Result<EccModeState, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetEccMode.as_ref())?;
-
-        unsafe {
-            let mut current: nvmlEnableState_t = mem::zeroed();
-            let mut pending: nvmlEnableState_t = mem::zeroed();
-
-            nvml_try(sym(self.device, &mut current, &mut pending))?;
+    // Tested
+    pub fn driver_model_or_none(&self) -> Result<Option<DriverModelState>, NvmlError> {
+        #[cfg(target_os = "windows")]
+        {
+            match self.driver_model() {
+                Ok(state) => Ok(Some(state)),
+                Err(NvmlError::NotSupported) => Ok(None),
+                Err(e) => Err(e),
+            }
+        }
 
-            Ok(EccModeState {
-                currently_enabled: bool_from_state(current)?,
-                pending_enabled: bool_from_state(pending)?,
-            })
+        #[cfg(not(target_os = "windows"))]
+        {
+            Ok(None)
         }
     }
 
     /**
-    Gets the current utilization and sampling size (sampling size in μs) for the Encoder.
+    Checks whether this `Device`'s pending driver model differs from the
+    current one.
+
+    A `true` result means that a driver model change has been requested via
+    `.set_driver_model()` and that a reboot is required for it to take effect.
 
     # Errors
 
@@ -1135,20 +1558,87 @@ impl<'nvml> Device<'nvml> {
     * `InvalidArg`, if this `Device` is invalid
     * `NotSupported`, if this `Device` does not support this feature
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
     * `Unknown`, on any unexpected error
 
     # Device Support
 
-    Supports Kepler or newer fully supported devices.
-    */
-    // Checked against local
-    // Tested
-    #[doc(alias = "nvmlDeviceGetEncoderUtilization")]
-    pub fn encoder_utilization(&self) -> Result<UtilizationInfo, NvmlError> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetEncoderUtilization.as_ref())?;
+    Supports Fermi and newer fully supported devices.
 
-        unsafe {
-            let mut utilization: c_uint = mem::zeroed();
+    # Platform Support
+
+    Only supports Windows.
+    */
+    // Tested (no-run)
+    #[cfg(target_os = "windows")]
+    pub fn driver_model_change_pending(&self) -> Result<bool, NvmlError> {
+        let state = self.driver_model()?;
+
+        Ok(state.current != state.pending)
+    }
+
+    /**
+    Get the current and pending ECC modes for this `Device`.
+
+    Changing ECC modes requires a reboot. The "pending" ECC mode refers to the target
+    mode following the next reboot.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Fermi and newer fully supported devices. Only applicable to devices with
+    ECC. Requires `InfoRom::ECC` version 1.0 or higher.
+    */
+    // Checked against local
+    // Tested on machines other than my own
+    #[doc(alias = "nvmlDeviceGetEccMode")]
+    pub fn is_ecc_enabled(&self) -> Result<EccModeState, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetEccMode.as_ref())?;
+
+        unsafe {
+            let mut current: nvmlEnableState_t = mem::zeroed();
+            let mut pending: nvmlEnableState_t = mem::zeroed();
+
+            nvml_try(sym(self.device, &mut current, &mut pending))?;
+
+            Ok(EccModeState {
+                currently_enabled: bool_from_state(current)?,
+                pending_enabled: bool_from_state(pending)?,
+            })
+        }
+    }
+
+    /**
+    Gets the current utilization and sampling size (sampling size in μs) for the Encoder.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler or newer fully supported devices.
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetEncoderUtilization")]
+    pub fn encoder_utilization(&self) -> Result<UtilizationInfo, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetEncoderUtilization.as_ref())?;
+
+        unsafe {
+            let mut utilization: c_uint = mem::zeroed();
             let mut sampling_period: c_uint = mem::zeroed();
 
             nvml_try(sym(self.device, &mut utilization, &mut sampling_period))?;
@@ -1189,6 +1679,41 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets the current capacity of this device's encoder in macroblocks per second
+    for every `EncoderType`.
+
+    Encoder types that this `Device` does not support are left out of the
+    returned map.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this device is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell or newer fully supported devices.
+    */
+    // Tested
+    pub fn encoder_capacities(&self) -> Result<HashMap<EncoderType, u32>, NvmlError> {
+        let mut capacities = HashMap::new();
+
+        for for_type in [EncoderType::H264, EncoderType::HEVC] {
+            match self.encoder_capacity(for_type.clone()) {
+                Ok(capacity) => {
+                    capacities.insert(for_type, capacity);
+                }
+                Err(NvmlError::NotSupported) => (),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(capacities)
+    }
+
     /**
     Gets the current encoder stats for this device.
 
@@ -1322,6 +1847,18 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Same as `.enforced_power_limit()`, but returns the limit in watts rather than milliwatts.
+
+    # Errors
+
+    See `.enforced_power_limit()`.
+    */
+    // Tested
+    pub fn enforced_power_limit_watts(&self) -> Result<f64, NvmlError> {
+        self.enforced_power_limit().map(milliwatts_to_watts)
+    }
+
     /**
     Gets the intended operating speed of the specified fan as a percentage of the
     maximum fan speed (100%).
@@ -1421,11 +1958,42 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Checks whether this `Device`'s pending GPU operation mode differs from the
+    current one.
+
+    A `true` result means that a GOM change has been requested via
+    `.set_gpu_op_mode()` and that a reboot is required for it to take effect.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports GK110 M-class and X-class Tesla products from the Kepler family. Modes `LowDP`
+    and `AllOn` are supported on fully supported GeForce products. Not supported
+    on Quadro and Tesla C-class products.
+    */
+    pub fn gpu_operation_mode_pending_differs(&self) -> Result<bool, NvmlError> {
+        let state = self.gpu_operation_mode()?;
+
+        Ok(state.current != state.pending)
+    }
+
     /**
     Gets information about processes with a graphics context running on this `Device`.
 
     This only returns information about graphics based processes (OpenGL, DirectX, etc.).
 
+    Returns an empty `Vec` if there are no such processes, including on
+    drivers that report that case as `NotFound`.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
@@ -1454,7 +2022,11 @@ impl<'nvml> Device<'nvml> {
             count += 5;
             let mut processes: Vec<nvmlProcessInfo_t> = vec![mem::zeroed(); count as usize];
 
-            nvml_try(sym(self.device, &mut count, processes.as_mut_ptr()))?;
+            match nvml_try(sym(self.device, &mut count, processes.as_mut_ptr())) {
+                // Some drivers report no processes as `NotFound`
+                Err(NvmlError::NotFound) => return Ok(vec![]),
+                other => other?,
+            }
             processes.truncate(count as usize);
 
             Ok(processes.into_iter().map(ProcessInfo::from).collect())
@@ -1466,6 +2038,9 @@ impl<'nvml> Device<'nvml> {
 
     This only returns the count of graphics based processes (OpenGL, DirectX).
 
+    Returns 0 if there are no such processes, including on drivers that
+    report that case as `NotFound`.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
@@ -1491,17 +2066,48 @@ impl<'nvml> Device<'nvml> {
             // Passing null doesn't indicate that we want the count. It's just allowed.
             match sym(self.device, &mut count, ptr::null_mut()) {
                 nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => Ok(count),
+                // Some drivers report no processes as `NotFound`
+                nvmlReturn_enum_NVML_ERROR_NOT_FOUND => Ok(0),
                 // If success, return 0; otherwise, return error
                 other => nvml_try(other).map(|_| 0),
             }
         }
     }
 
+    /**
+    Gets the total amount of GPU memory used by processes running on this
+    `Device`, in bytes.
+
+    This sums the used memory reported for every process returned by
+    `.running_compute_processes()` and `.running_graphics_processes()`. A
+    process that appears in both lists is only counted once, and processes whose
+    memory usage is `UsedGpuMemory::Unavailable` (e.g. when running in WDDM mode
+    on Windows) are skipped, so the result may be lower than the `used` value
+    reported by `.memory_info()`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    // Tested
+    pub fn total_process_used_memory(&self) -> Result<u64, NvmlError> {
+        let compute = self.running_compute_processes()?;
+        let graphics = self.running_graphics_processes()?;
+
+        Ok(sum_used_gpu_memory(compute.iter().chain(graphics.iter())))
+    }
+
     /**
     Gets information about processes with a graphics context running on this `Device`.
 
     This only returns information about graphics based processes (OpenGL, DirectX, etc.).
 
+    Returns an empty `Vec` if there are no such processes, including on
+    drivers that report that case as `NotFound`.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
@@ -1530,7 +2136,11 @@ impl<'nvml> Device<'nvml> {
             count += 5;
             let mut processes: Vec<nvmlProcessInfo_v2_t> = vec![mem::zeroed(); count as usize];
 
-            nvml_try(sym(self.device, &mut count, processes.as_mut_ptr()))?;
+            match nvml_try(sym(self.device, &mut count, processes.as_mut_ptr())) {
+                // Some drivers report no processes as `NotFound`
+                Err(NvmlError::NotFound) => return Ok(vec![]),
+                other => other?,
+            }
             processes.truncate(count as usize);
 
             Ok(processes.into_iter().map(ProcessInfo::from).collect())
@@ -1542,6 +2152,9 @@ impl<'nvml> Device<'nvml> {
 
     This only returns the count of graphics based processes (OpenGL, DirectX).
 
+    Returns 0 if there are no such processes, including on drivers that
+    report that case as `NotFound`.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
@@ -1567,6 +2180,8 @@ impl<'nvml> Device<'nvml> {
             // Passing null doesn't indicate that we want the count. It's just allowed.
             match sym(self.device, &mut count, ptr::null_mut()) {
                 nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => Ok(count),
+                // Some drivers report no processes as `NotFound`
+                nvmlReturn_enum_NVML_ERROR_NOT_FOUND => Ok(0),
                 // If success, return 0; otherwise, return error
                 other => nvml_try(other).map(|_| 0),
             }
@@ -1593,6 +2208,11 @@ impl<'nvml> Device<'nvml> {
     # Device Support
 
     Supports Maxwell or newer fully supported devices.
+
+    Processes can start between the call that fetches the number of samples and
+    the call that fetches the samples themselves. If NVML reports that the
+    buffer is too small, the call is retried with the size NVML says is required
+    (up to a few times, after which `InsufficientSize` is returned).
     */
     #[doc(alias = "nvmlDeviceGetProcessUtilization")]
     pub fn process_utilization_stats<T>(
@@ -1602,23 +2222,76 @@ impl<'nvml> Device<'nvml> {
     where
         T: Into<Option<u64>>,
     {
+        let last_seen_timestamp = last_seen_timestamp.into().unwrap_or(0);
+        let count = match self.process_utilization_stats_count()? {
+            0 => return Ok(vec![]),
+            v => v,
+        };
+
+        retry_with_required_size(count as usize, |size| {
+            self.process_utilization_stats_manual(last_seen_timestamp, size)
+        })
+    }
+
+    /**
+    Gets the utilization stats for the process with the given `pid`, if it had
+    a non-zero utilization stat during the target sample period.
+
+    This calls `.process_utilization_stats()`; see it for what
+    `last_seen_timestamp` does. If the driver returns more than one sample for
+    the process, the most recent one is returned.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell or newer fully supported devices.
+    */
+    // Tested
+    pub fn process_utilization_for_pid<T>(
+        &self,
+        pid: u32,
+        last_seen_timestamp: T,
+    ) -> Result<Option<ProcessUtilizationSample>, NvmlError>
+    where
+        T: Into<Option<u64>>,
+    {
+        let samples = self.process_utilization_stats(last_seen_timestamp)?;
+
+        Ok(latest_sample_for_pid(samples, pid))
+    }
+
+    // Removes code duplication in the above function.
+    fn process_utilization_stats_manual(
+        &self,
+        last_seen_timestamp: u64,
+        size: usize,
+    ) -> Result<Vec<ProcessUtilizationSample>, NvmlError> {
         let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetProcessUtilization.as_ref())?;
 
         unsafe {
-            let last_seen_timestamp = last_seen_timestamp.into().unwrap_or(0);
-            let mut count = match self.process_utilization_stats_count()? {
-                0 => return Ok(vec![]),
-                v => v,
-            };
+            let mut count = size as c_uint;
             let mut utilization_samples: Vec<nvmlProcessUtilizationSample_t> =
-                vec![mem::zeroed(); count as usize];
+                vec![mem::zeroed(); size];
 
-            nvml_try(sym(
+            match sym(
                 self.device,
                 utilization_samples.as_mut_ptr(),
                 &mut count,
                 last_seen_timestamp,
-            ))?;
+            ) {
+                // `count` is now the size that is required. Return it in the error.
+                nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => {
+                    return Err(NvmlError::InsufficientSize(Some(count as usize)))
+                }
+                value => nvml_try(value)?,
+            }
             utilization_samples.truncate(count as usize);
 
             Ok(utilization_samples
@@ -1824,6 +2497,76 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Same as `.max_clock_info()`, but returns the clock as a `Frequency`.
+
+    # Errors
+
+    See `.max_clock_info()`.
+    */
+    // Tested
+    pub fn max_clock_info_typed(&self, clock_type: Clock) -> Result<Frequency, NvmlError> {
+        self.max_clock_info(clock_type).map(Frequency)
+    }
+
+    /**
+    Checks whether the specified `Clock` is currently running significantly
+    below its maximum.
+
+    This compares `.clock_info()` against `.max_clock_info()` and returns `true`
+    if the current clock is more than 5% below the max. The tolerance absorbs the
+    few MHz of difference that some devices report while running at full speed.
+
+    Combine this with `.current_throttle_reasons()` to find out why a clock is
+    being held back.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` cannot report the specified `Clock`
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Fermi and newer fully supported devices.
+    */
+    // Tested
+    pub fn is_clock_throttled(&self, clock_type: Clock) -> Result<bool, NvmlError> {
+        let current = self.clock_info(clock_type.clone())?;
+        let max = self.max_clock_info(clock_type)?;
+
+        Ok(clock_below_max(current, max))
+    }
+
+    /**
+    Checks whether this `Device` is currently boosting its graphics clock.
+
+    This compares the current graphics clock from `.clock_info()` against
+    `.default_applications_clock()` and returns `true` if the current clock is
+    above the default, which means auto boost is raising it.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler or newer fully supported devices.
+    */
+    // Tested
+    pub fn is_boosting(&self) -> Result<bool, NvmlError> {
+        let current = self.clock_info(Clock::Graphics)?;
+        let default = self.default_applications_clock(Clock::Graphics)?;
+
+        Ok(clock_above_default(current, default))
+    }
+
     /**
     Gets the max PCIe link generation possible with this `Device` and system.
 
@@ -1857,6 +2600,38 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets the max PCIe link generation supported by this `Device`, irrespective
+    of the system it is attached to.
+
+    Unlike `.max_pcie_link_gen()`, this is not limited by the PCIe slot. If the
+    value returned here is higher than the one returned by
+    `.max_pcie_link_gen()`, the slot is the bottleneck.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if PCIe link information is not available
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `FunctionNotFound`, if the installed driver does not provide this function
+    * `FailedToLoadSymbol`, if the loaded NVML library does not export this function
+    * `Unknown`, on any unexpected error
+    */
+    // Tested
+    #[doc(alias = "nvmlDeviceGetGpuMaxPcieLinkGeneration")]
+    pub fn gpu_max_pcie_link_gen(&self) -> Result<u32, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetGpuMaxPcieLinkGeneration.as_ref())?;
+
+        unsafe {
+            let mut max_gen: c_uint = mem::zeroed();
+
+            nvml_try(sym(self.device, &mut max_gen))?;
+
+            Ok(max_gen)
+        }
+    }
+
     /**
     Gets the maximum PCIe link width possible with this `Device` and system.
 
@@ -2003,7 +2778,73 @@ impl<'nvml> Device<'nvml> {
     }
 
     /**
-    Identifies whether or not this `Device` is on a multi-GPU board.
+    Gets an identifier for this `Device` that is stable for as long as the
+    system stays up.
+
+    The value returned differs per platform:
+
+    * On Linux, this is the `Device`'s minor number (see `.minor_number()`)
+    * Elsewhere, this is the `Device`'s PCI bus ID (see `.pci_info()`)
+
+    The two are not comparable with each other; only compare identifiers
+    obtained on the same platform. Neither is guaranteed to survive a reboot.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this query is not supported by this `Device`
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Utf8Error`, if a string obtained from the C function is not valid Utf8
+    * `Unknown`, on any unexpected error
+    */
+    pub fn stable_local_id(&self) -> Result<String, NvmlError> {
+        #[cfg(target_os = "linux")]
+        {
+            Ok(self.minor_number()?.to_string())
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        {
+            Ok(self.pci_info()?.bus_id)
+        }
+    }
+
+    /**
+    Gets the NUMA node that this `Device` is attached to.
+
+    NVML doesn't expose this, so it is read from
+    `/sys/bus/pci/devices/<pci bus id>/numa_node`. `None` is returned if the
+    system doesn't have NUMA (the kernel reports `-1` or doesn't provide the
+    file).
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Utf8Error`, if a string obtained from the C function is not valid Utf8
+    * `OperatingSystem`, if the sysfs file exists but could not be read
+    * `Unknown`, if the PCI bus ID or the sysfs file contents could not be parsed
+
+    # Platform Support
+
+    Only supports Linux.
+    */
+    #[cfg(target_os = "linux")]
+    pub fn numa_node(&self) -> Result<Option<i32>, NvmlError> {
+        let address = sysfs_pci_address(&self.pci_info()?.bus_id).ok_or(NvmlError::Unknown)?;
+        let path = format!("/sys/bus/pci/devices/{}/numa_node", address);
+
+        match std::fs::read_to_string(path) {
+            Ok(contents) => parse_numa_node(&contents),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(_) => Err(NvmlError::OperatingSystem),
+        }
+    }
+
+    /**
+    Identifies whether or not this `Device` is on a multi-GPU board.
 
     # Errors
 
@@ -2094,6 +2935,62 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Checks whether this `Device` is PCI function 0.
+
+    Multi-function boards expose additional PCI functions alongside the GPU;
+    this can be used to keep only the primary function of each board when
+    deduplicating a list of devices. The function number is read from the bus
+    ID returned by `.pci_info()`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `GpuLost`, if the GPU has fallen off the bus or is otherwise inaccessible
+    * `Utf8Error`, if a string obtained from the C function is not valid Utf8
+    * `Unknown`, on any unexpected error, including a bus ID without a function number
+    */
+    // Tested
+    pub fn is_function_zero(&self) -> Result<bool, NvmlError> {
+        match self.pci_info()?.function() {
+            Some(function) => Ok(function == 0),
+            None => Err(NvmlError::Unknown),
+        }
+    }
+
+    /**
+    Checks that looking this `Device` up by its own PCI bus ID finds this
+    `Device` again.
+
+    The bus ID from `.pci_info()` is passed through `Nvml.device_by_pci_bus_id()`
+    and the handle that comes back is compared with this one. Returns `false`
+    if the lookup finds a different `Device` or none at all (`NotFound`).
+    Useful for diagnosing bus ID lookups that unexpectedly fail.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `GpuLost`, if the GPU has fallen off the bus or is otherwise inaccessible
+    * `Utf8Error`, if a string obtained from the C function is not valid Utf8
+    * `NulError`, for which you can read the docs on `std::ffi::NulError`
+    * `Unknown`, on any unexpected error
+
+    In addition, all of the errors other than `NotFound` returned by
+    `Nvml.device_by_pci_bus_id()`.
+    */
+    // Tested
+    pub fn verify_pci_bus_id(&self) -> Result<bool, NvmlError> {
+        let bus_id = self.pci_info()?.bus_id;
+
+        match self.nvml.device_by_pci_bus_id(bus_id) {
+            Ok(device) => Ok(device == *self),
+            Err(NvmlError::NotFound) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
     /**
     Gets the PCIe replay counter.
 
@@ -2123,6 +3020,64 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets the PCIe replay counter along with this `Device`'s PCIe error counts.
+
+    The error counts are read in a single batch via `.field_values_for()`.
+    Counters that this `Device` does not report (e.g. because the driver is too
+    old or the field is not supported on this architecture) are `None` in the
+    returned `PcieErrorSummary` rather than causing the entire call to fail.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support the PCIe replay counter
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler or newer fully supported devices.
+    */
+    pub fn pcie_replay_and_errors(&self) -> Result<PcieErrorSummary, NvmlError> {
+        let replay_count = self.pcie_replay_counter()?;
+
+        let ids = [
+            FieldId(NVML_FI_DEV_PCIE_REPLAY_ROLLOVER_COUNTER),
+            FieldId(NVML_FI_DEV_PCIE_COUNT_CORRECTABLE_ERRORS),
+            FieldId(NVML_FI_DEV_PCIE_COUNT_NON_FATAL_ERROR),
+            FieldId(NVML_FI_DEV_PCIE_COUNT_FATAL_ERROR),
+            FieldId(NVML_FI_DEV_PCIE_COUNT_UNSUPPORTED_REQ),
+        ];
+
+        let mut counts = match self.field_values_for(&ids) {
+            Ok(samples) => samples
+                .into_iter()
+                .map(|sample| match sample.and_then(|s| s.value) {
+                    Ok(SampleValue::U32(v)) => Some(v as u64),
+                    Ok(SampleValue::U64(v)) => Some(v),
+                    Ok(SampleValue::I64(v)) => u64::try_from(v).ok(),
+                    _ => None,
+                })
+                .collect(),
+            Err(NvmlError::NotSupported) | Err(NvmlError::FunctionNotFound) => vec![],
+            Err(e) => return Err(e),
+        }
+        .into_iter();
+
+        let mut next = || counts.next().flatten();
+
+        Ok(PcieErrorSummary {
+            replay_count,
+            replay_rollover_count: next(),
+            correctable_errors: next(),
+            non_fatal_errors: next(),
+            fatal_errors: next(),
+            unsupported_requests: next(),
+        })
+    }
+
     /**
     Gets PCIe utilization information in KB/s.
 
@@ -2160,6 +3115,55 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets PCIe utilization information in KB/s, averaged over `samples` reads
+    taken `interval` apart.
+
+    `.pcie_throughput()` reflects a 20ms window and is therefore quite noisy;
+    this method smooths that out at the cost of blocking the calling thread for
+    `interval * (samples - 1)`. With `samples == 1` this is equivalent to a
+    single call to `.pcie_throughput()` and does not sleep.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if `samples` is zero, or if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell and newer fully supported devices.
+
+    # Environment Support
+
+    This method is not supported on virtual machines running vGPUs.
+    */
+    #[doc(alias = "nvmlDeviceGetPcieThroughput")]
+    pub fn pcie_throughput_averaged(
+        &self,
+        counter: PcieUtilCounter,
+        samples: u32,
+        interval: Duration,
+    ) -> Result<u32, NvmlError> {
+        if samples == 0 {
+            return Err(NvmlError::InvalidArg);
+        }
+
+        let mut total: u64 = 0;
+
+        for i in 0..samples {
+            if i != 0 {
+                thread::sleep(interval);
+            }
+
+            total += u64::from(self.pcie_throughput(counter.clone())?);
+        }
+
+        Ok((total / u64::from(samples)) as u32)
+    }
+
     /**
     Gets the current performance state for this `Device`. 0 == max, 15 == min.
 
@@ -2296,6 +3300,18 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Same as `.power_management_limit()`, but returns the limit in watts rather than milliwatts.
+
+    # Errors
+
+    See `.power_management_limit()`.
+    */
+    // Tested
+    pub fn power_management_limit_watts(&self) -> Result<f64, NvmlError> {
+        self.power_management_limit().map(milliwatts_to_watts)
+    }
+
     /**
     Gets information about possible power management limit values for this `Device`, in milliwatts.
 
@@ -2340,7 +3356,7 @@ impl<'nvml> Device<'nvml> {
     /// Not documenting this because it's deprecated. Read NVIDIA's docs if you
     /// must use it.
     // Tested
-    #[deprecated(note = "NVIDIA states that \"this API has been deprecated.\"")]
+    #[deprecated(note = "use `.is_power_management_supported()`.")]
     #[doc(alias = "nvmlDeviceGetPowerManagementMode")]
     pub fn is_power_management_algo_active(&self) -> Result<bool, NvmlError> {
         let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetPowerManagementMode.as_ref())?;
@@ -2353,6 +3369,33 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Checks whether or not this `Device` supports power management.
+
+    This supersedes the deprecated `.is_power_management_algo_active()`. It
+    queries `.power_management_limit_constraints()` and treats a `NotSupported`
+    error as `false`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler or newer fully supported devices.
+    */
+    // Tested
+    pub fn is_power_management_supported(&self) -> Result<bool, NvmlError> {
+        match self.power_management_limit_constraints() {
+            Ok(_) => Ok(true),
+            Err(NvmlError::NotSupported) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Not documenting this because it's deprecated. Read NVIDIA's docs if you
     /// must use it.
     // Tested
@@ -2387,6 +3430,8 @@ impl<'nvml> Device<'nvml> {
     This reading is accurate to within +/- 5% of current power draw on Fermi and Kepler GPUs.
     It is only supported if power management mode is supported. See `.is_power_management_algo_active()`.
     Yes, that is deprecated, but that's what NVIDIA's docs say to see.
+
+    This is an instantaneous reading. See `.average_power_usage()` for a smoothed one.
     */
     // Checked against local
     // Tested
@@ -2402,6 +3447,172 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Same as `.power_usage()`, but returns the usage in watts rather than milliwatts.
+
+    # Errors
+
+    See `.power_usage()`.
+    */
+    // Tested
+    pub fn power_usage_watts(&self) -> Result<f64, NvmlError> {
+        self.power_usage().map(milliwatts_to_watts)
+    }
+
+    /**
+    Gets this `Device`'s instantaneous and averaged power draw along with its
+    power limits, in milliwatts.
+
+    The values are read in a single batch via `.field_values_for()`. Values
+    that this `Device` does not report (e.g. because the driver is too old) are
+    `None` in the returned `PowerScalingInfo` rather than causing the entire
+    call to fail.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    // Tested
+    pub fn power_scaling_info(&self) -> Result<PowerScalingInfo, NvmlError> {
+        let ids = [
+            FieldId(NVML_FI_DEV_POWER_INSTANT),
+            FieldId(NVML_FI_DEV_POWER_AVERAGE),
+            FieldId(NVML_FI_DEV_POWER_MIN_LIMIT),
+            FieldId(NVML_FI_DEV_POWER_MAX_LIMIT),
+            FieldId(NVML_FI_DEV_POWER_CURRENT_LIMIT),
+        ];
+
+        let mut values = match self.field_values_for(&ids) {
+            Ok(samples) => samples
+                .into_iter()
+                .map(|sample| match sample.and_then(|s| s.value) {
+                    Ok(SampleValue::U32(v)) => Some(v),
+                    Ok(SampleValue::U64(v)) => u32::try_from(v).ok(),
+                    Ok(SampleValue::I64(v)) => u32::try_from(v).ok(),
+                    _ => None,
+                })
+                .collect(),
+            Err(NvmlError::NotSupported) | Err(NvmlError::FunctionNotFound) => vec![],
+            Err(e) => return Err(e),
+        }
+        .into_iter();
+
+        let mut next = || values.next().flatten();
+
+        Ok(PowerScalingInfo {
+            instant: next(),
+            average: next(),
+            min_limit: next(),
+            max_limit: next(),
+            current_limit: next(),
+        })
+    }
+
+    /**
+    Gets the fraction of the enforced power limit that this `Device` is
+    currently drawing (`.power_usage() / .enforced_power_limit()`).
+
+    A value of 1.0 means this `Device` is running at its power limit; values
+    can briefly exceed 1.0. Returns 0.0 if the enforced power limit is 0.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support power readings
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    // Tested
+    pub fn power_headroom(&self) -> Result<f64, NvmlError> {
+        let usage = self.power_usage()?;
+        let limit = self.enforced_power_limit()?;
+
+        Ok(fraction(usage, limit))
+    }
+
+    /**
+    Gets the current power management limit as a fraction of the default limit
+    (`.power_management_limit() / .power_management_limit_default()`).
+
+    Values below 1.0 mean the limit has been lowered and values above 1.0 mean
+    it has been raised. Returns 0.0 if the default limit is 0.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    // Tested
+    pub fn power_limit_fraction_of_default(&self) -> Result<f64, NvmlError> {
+        let limit = self.power_management_limit()?;
+        let default = self.power_management_limit_default()?;
+
+        Ok(fraction(limit, default))
+    }
+
+    /**
+    Gets the power usage for this GPU and its associated circuitry (memory) in
+    milliwatts, averaged over `window`.
+
+    `.power_usage()` is an instantaneous reading; this method smooths it out by
+    reading it once immediately and then every `interval` until `window` has
+    elapsed, blocking the calling thread for that long. If `window` is shorter
+    than `interval` a single reading is taken and the method does not sleep.
+
+    The same accuracy caveat applies as for `.power_usage()`: each reading is
+    accurate to within +/- 5% of current power draw on Fermi and Kepler GPUs.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if `interval` is zero, or if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support power readings
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Fermi and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetPowerUsage")]
+    pub fn average_power_usage(
+        &self,
+        window: Duration,
+        interval: Duration,
+    ) -> Result<u32, NvmlError> {
+        if interval.is_zero() {
+            return Err(NvmlError::InvalidArg);
+        }
+
+        let samples = (window.as_nanos() / interval.as_nanos()) as u64 + 1;
+        let mut total: u64 = 0;
+
+        for i in 0..samples {
+            if i != 0 {
+                thread::sleep(interval);
+            }
+
+            total += u64::from(self.power_usage()?);
+        }
+
+        Ok((total / samples) as u32)
+    }
+
     /**
     Gets this device's total energy consumption in millijoules (mJ) since the last
     driver reload.
@@ -2537,32 +3748,80 @@ impl<'nvml> Device<'nvml> {
     }
 
     /**
-    Gets recent samples for this `Device`.
-
-    `last_seen_timestamp` represents the CPU timestamp in μs. Passing in `None`
-    will fetch all samples maintained in the underlying buffer; you can
-    alternatively pass in a timestamp retrieved from the date of the previous
-    query in order to obtain more recent samples.
+    Gets whether there are pages pending retirement (they need a reboot to
+    fully retire) for each retirement cause.
 
-    The advantage of using this method for samples in contrast to polling via
-    existing methods is to get higher frequency data at a lower polling cost.
+    The retired pages API doesn't report a per-page pending status, so this
+    reads the `NVML_FI_DEV_RETIRED_PENDING_SBE` and
+    `NVML_FI_DEV_RETIRED_PENDING_DBE` field values instead. NVML reports each
+    of those as a flag rather than a count of pages.
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
     * `InvalidArg`, if this `Device` is invalid
-    * `NotSupported`, if this query is not supported by this `Device`
+    * `NotSupported`, if this `Device` doesn't support this feature
     * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
-    * `NotFound`, if sample entries are not found
-    * `UnexpectedVariant`, check that error's docs for more info
+    * `UnexpectedVariant`, for which you can read the docs for
     * `Unknown`, on any unexpected error
 
     # Device Support
 
     Supports Kepler and newer fully supported devices.
-
-    # Examples
-
+    */
+    pub fn pending_retired_pages(&self) -> Result<PendingRetiredPages, NvmlError> {
+        let mut flags = self
+            .field_values_for(&[
+                FieldId(NVML_FI_DEV_RETIRED_PENDING_SBE),
+                FieldId(NVML_FI_DEV_RETIRED_PENDING_DBE),
+            ])?
+            .into_iter()
+            .map(|sample| {
+                let value = sample?.value?.to_u32()?;
+
+                match value {
+                    0 => Ok(false),
+                    1 => Ok(true),
+                    _ => Err(NvmlError::UnexpectedVariant(value)),
+                }
+            });
+
+        match (flags.next(), flags.next()) {
+            (Some(single_bit), Some(double_bit)) => Ok(PendingRetiredPages {
+                single_bit: single_bit?,
+                double_bit: double_bit?,
+            }),
+            _ => Err(NvmlError::Unknown),
+        }
+    }
+
+    /**
+    Gets recent samples for this `Device`.
+
+    `last_seen_timestamp` represents the CPU timestamp in μs. Passing in `None`
+    will fetch all samples maintained in the underlying buffer; you can
+    alternatively pass in a timestamp retrieved from the date of the previous
+    query in order to obtain more recent samples.
+
+    The advantage of using this method for samples in contrast to polling via
+    existing methods is to get higher frequency data at a lower polling cost.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this query is not supported by this `Device`
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `NotFound`, if sample entries are not found
+    * `UnexpectedVariant`, check that error's docs for more info
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+
+    # Examples
+
     ```
     # use nvml_wrapper::Nvml;
     # use nvml_wrapper::error::*;
@@ -2602,15 +3861,165 @@ impl<'nvml> Device<'nvml> {
         T: Into<Option<u64>>,
     {
         let timestamp = last_seen_timestamp.into().unwrap_or(0);
+        let mut samples = vec![];
+
+        self.samples_and_type(sample_type, timestamp, &mut vec![], &mut samples)?;
+
+        Ok(samples)
+    }
+
+    /**
+    Gets recent samples for this `Device`, writing them into the given buffer.
+
+    This is the same as `.samples()`, but `buf` is cleared and then filled with
+    the samples instead of a new `Vec` being returned. Reusing one buffer across
+    calls avoids allocating the returned `Vec` for each poll; `buf` only grows
+    when more samples are returned than it has room for.
+
+    The buffer that NVML writes the raw samples into is still allocated on
+    every call. Use `.samples_into_buffer()` to reuse that as well.
+
+    `buf` is left empty if an error is returned.
+
+    # Errors
+
+    See `.samples()`.
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    // Tested
+    #[doc(alias = "nvmlDeviceGetSamples")]
+    pub fn samples_into<T>(
+        &self,
+        sample_type: Sampling,
+        last_seen_timestamp: T,
+        buf: &mut Vec<Sample>,
+    ) -> Result<(), NvmlError>
+    where
+        T: Into<Option<u64>>,
+    {
+        let timestamp = last_seen_timestamp.into().unwrap_or(0);
+
+        buf.clear();
+        self.samples_and_type(sample_type, timestamp, &mut vec![], buf)?;
+
+        Ok(())
+    }
+
+    /**
+    Gets recent samples for this `Device`, writing them into the given
+    `SampleBuffer`.
+
+    This is the same as `.samples_into()`, but the buffer that NVML writes the
+    raw samples into is kept in `buf` as well, so that polling in a tight loop
+    with the same `SampleBuffer` doesn't allocate at all once it has room for
+    the number of samples returned. Read the samples via `buf.samples()`.
+
+    `buf` holds no samples if an error is returned.
+
+    # Errors
+
+    See `.samples()`.
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    // Tested
+    #[doc(alias = "nvmlDeviceGetSamples")]
+    pub fn samples_into_buffer<T>(
+        &self,
+        sample_type: Sampling,
+        last_seen_timestamp: T,
+        buf: &mut SampleBuffer,
+    ) -> Result<(), NvmlError>
+    where
+        T: Into<Option<u64>>,
+    {
+        let timestamp = last_seen_timestamp.into().unwrap_or(0);
+
+        buf.samples.clear();
+        self.samples_and_type(sample_type, timestamp, &mut buf.raw, &mut buf.samples)?;
+
+        Ok(())
+    }
+
+    /**
+    Gets recent samples for this `Device` with values of type `V`.
+
+    This is the same as `.samples()`, but checks that NVML returned the samples
+    as type `V` so that the values don't need to be matched on. See `.samples()`
+    for what `last_seen_timestamp` does.
+
+    Use the value type that the chosen `Sampling` type is reported as (e.g.
+    `u32` for utilization samples). If there are no samples an empty `Vec` is
+    returned without the type being checked.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this query is not supported by this `Device`
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `NotFound`, if sample entries are not found
+    * `SampleValueTypeMismatch`, if NVML returned the samples as a type other than `V`
+    * `UnexpectedVariant`, check that error's docs for more info
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetSamples")]
+    pub fn samples_typed<V, T>(
+        &self,
+        sample_type: Sampling,
+        last_seen_timestamp: T,
+    ) -> Result<Vec<TypedSample<V>>, NvmlError>
+    where
+        V: FromSampleValue,
+        T: Into<Option<u64>>,
+    {
+        let timestamp = last_seen_timestamp.into().unwrap_or(0);
+
+        let mut samples = vec![];
+        let value_type =
+            match self.samples_and_type(sample_type, timestamp, &mut vec![], &mut samples)? {
+                Some(v) => v,
+                None => return Ok(vec![]),
+            };
+
+        samples
+            .into_iter()
+            .map(|s| {
+                TypedSample::from_sample(s)
+                    .ok_or_else(|| NvmlError::SampleValueTypeMismatch(value_type.clone()))
+            })
+            .collect()
+    }
+
+    // Helper for the above functions. Has NVML write the raw samples into
+    // `raw`, appends them to `out` and returns their value type, or `None` if
+    // there are no samples.
+    fn samples_and_type(
+        &self,
+        sample_type: Sampling,
+        timestamp: u64,
+        raw: &mut Vec<nvmlSample_t>,
+        out: &mut Vec<Sample>,
+    ) -> Result<Option<SampleValueType>, NvmlError> {
         let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetSamples.as_ref())?;
 
         unsafe {
             let mut val_type: nvmlValueType_t = mem::zeroed();
             let mut count = match self.samples_count(&sample_type, timestamp)? {
-                0 => return Ok(vec![]),
+                0 => return Ok(None),
                 value => value,
             };
-            let mut samples: Vec<nvmlSample_t> = vec![mem::zeroed(); count as usize];
+            raw.clear();
+            raw.resize(count as usize, mem::zeroed());
 
             nvml_try(sym(
                 self.device,
@@ -2618,14 +4027,19 @@ impl<'nvml> Device<'nvml> {
                 timestamp,
                 &mut val_type,
                 &mut count,
-                samples.as_mut_ptr(),
+                raw.as_mut_ptr(),
             ))?;
 
             let val_type_rust = SampleValueType::try_from(val_type)?;
-            Ok(samples
-                .into_iter()
-                .map(|s| Sample::from_tag_and_struct(&val_type_rust, s))
-                .collect())
+            // NVML may return fewer samples than it reported space was needed for
+            raw.truncate(count as usize);
+
+            out.extend(
+                raw.iter()
+                    .map(|s| Sample::from_tag_and_struct(&val_type_rust, *s)),
+            );
+
+            Ok(Some(val_type_rust))
         }
     }
 
@@ -2684,8 +4098,152 @@ impl<'nvml> Device<'nvml> {
         &self,
         id_slice: &[FieldId],
     ) -> Result<Vec<Result<FieldValueSample, NvmlError>>, NvmlError> {
+        self.field_values_for_raw(id_slice.iter().map(|id| (id.0, 0)))
+    }
+
+    /**
+    Get values for the given slice of `FieldId`s, each paired with the scope ID
+    to query it for.
+
+    The meaning of a scope ID depends on the field; for example, it selects the
+    MIG instance for per-instance fields and the link for per-NvLink fields.
+    The scope ID is reported back in `FieldValueSample.scope_id`. Passing a
+    scope ID of 0 for every field is the same as calling `.field_values_for()`.
+
+    # Errors
+
+    ## Outer `Result`
+
+    * `InvalidArg`, if `fields` has a length of zero
+
+    ## Inner `Result`
+
+    * `UnexpectedVariant`, check that error's docs for more info
+
+    # Device Support
+
+    Device support varies per `FieldId` that you pass in.
+    */
+    // Tested
+    #[doc(alias = "nvmlDeviceGetFieldValues")]
+    pub fn field_values_for_scoped(
+        &self,
+        fields: &[(FieldId, u32)],
+    ) -> Result<Vec<Result<FieldValueSample, NvmlError>>, NvmlError> {
+        self.field_values_for_raw(fields.iter().map(|(id, scope_id)| (id.0, *scope_id)))
+    }
+
+    // Removes code duplication in the above two functions; takes
+    // `(field_id, scope_id)` pairs.
+    fn field_values_for_raw<I>(
+        &self,
+        fields: I,
+    ) -> Result<Vec<Result<FieldValueSample, NvmlError>>, NvmlError>
+    where
+        I: ExactSizeIterator<Item = (u32, u32)>,
+    {
         let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetFieldValues.as_ref())?;
 
+        unsafe {
+            let values_count = fields.len();
+            let mut field_values: Vec<nvmlFieldValue_t> = Vec::with_capacity(values_count);
+
+            for (field_id, scope_id) in fields {
+                let mut raw: nvmlFieldValue_t = mem::zeroed();
+                raw.fieldId = field_id;
+                raw.scopeId = scope_id;
+
+                field_values.push(raw);
+            }
+
+            nvml_try(sym(
+                self.device,
+                values_count as i32,
+                field_values.as_mut_ptr(),
+            ))?;
+
+            Ok(field_values
+                .into_iter()
+                .map(FieldValueSample::try_from)
+                .collect())
+        }
+    }
+
+    /**
+    Get values for the given slice of `FieldId`s, querying at most `chunk_size`
+    of them per driver call.
+
+    Some drivers misbehave when handed very large arrays; this splits `ids`
+    into chunks, calls `.field_values_for()` for each, and concatenates the
+    results. The returned `Vec` has the same order as `ids`.
+
+    # Errors
+
+    ## Outer `Result`
+
+    * `InvalidArg`, if `ids` has a length of zero or `chunk_size` is zero
+    * Any error returned by `.field_values_for()` for one of the chunks
+
+    ## Inner `Result`
+
+    * `UnexpectedVariant`, check that error's docs for more info
+
+    # Device Support
+
+    Device support varies per `FieldId` that you pass in.
+    */
+    pub fn field_values_chunked(
+        &self,
+        ids: &[FieldId],
+        chunk_size: usize,
+    ) -> Result<Vec<Result<FieldValueSample, NvmlError>>, NvmlError> {
+        if ids.is_empty() || chunk_size == 0 {
+            return Err(NvmlError::InvalidArg);
+        }
+
+        let mut samples = Vec::with_capacity(ids.len());
+
+        for chunk in ids.chunks(chunk_size) {
+            samples.extend(self.field_values_for(chunk)?);
+        }
+
+        Ok(samples)
+    }
+
+    /**
+    Clear values for the given slice of `FieldId`s.
+
+    Some field values are accumulating counters; this resets them so that
+    rate-style metrics can be computed from subsequent reads via
+    `.field_values_for()`.
+
+    The inner `Result`s are returned in the same order as `id_slice` and
+    indicate whether each individual field was cleared.
+
+    # Errors
+
+    ## Outer `Result`
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if `id_slice` has a length of zero or this `Device` is invalid
+    * `Unknown`, on any unexpected error
+
+    ## Inner `Result`
+
+    * `NotSupported`, if the given field cannot be cleared on this `Device`
+    * `NoPermission`, if the user doesn't have permission to clear the field
+
+    # Device Support
+
+    Device support varies per `FieldId` that you pass in.
+    */
+    #[doc(alias = "nvmlDeviceClearFieldValues")]
+    pub fn clear_field_values(
+        &mut self,
+        id_slice: &[FieldId],
+    ) -> Result<Vec<Result<(), NvmlError>>, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceClearFieldValues.as_ref())?;
+
         unsafe {
             let values_count = id_slice.len();
             let mut field_values: Vec<nvmlFieldValue_t> = Vec::with_capacity(values_count);
@@ -2705,11 +4263,67 @@ impl<'nvml> Device<'nvml> {
 
             Ok(field_values
                 .into_iter()
-                .map(FieldValueSample::try_from)
+                .map(|v| nvml_try(v.nvmlReturn))
                 .collect())
         }
     }
 
+    /// Reads the value of a single field via `.field_values_for()`.
+    fn single_field_value(&self, id: u32) -> Result<SampleValue, NvmlError> {
+        match self.field_values_for(&[FieldId(id)])?.pop() {
+            Some(sample) => sample?.value,
+            None => Err(NvmlError::Unknown),
+        }
+    }
+
+    /**
+    Gets the current memory temperature of this `Device`, in °C.
+
+    This reads the `NVML_FI_DEV_MEMORY_TEMP` field value.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not report its memory temperature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error, including a value that doesn't fit in a `u32`
+
+    # Device Support
+
+    Supports Volta and newer fully supported devices with HBM memory.
+    */
+    // Tested
+    pub fn memory_temperature(&self) -> Result<u32, NvmlError> {
+        self.single_field_value(NVML_FI_DEV_MEMORY_TEMP)?.to_u32()
+    }
+
+    /**
+    Gets the common NvLink speed shared by all of this `Device`'s active links,
+    in MBps.
+
+    This reads the `NVML_FI_DEV_NVLINK_SPEED_MBPS_COMMON` field value.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support NvLink
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error, including a value that doesn't fit in a `u32`
+
+    # Device Support
+
+    Supports Pascal and newer fully supported devices with NvLink.
+    */
+    // Tested
+    pub fn nvlink_speed_mbps_common(&self) -> Result<u32, NvmlError> {
+        self.single_field_value(NVML_FI_DEV_NVLINK_SPEED_MBPS_COMMON)?
+            .to_u32()
+    }
+
     /**
     Gets the globally unique board serial number associated with this `Device`'s board
     as an alphanumeric string.
@@ -2857,9 +4471,93 @@ impl<'nvml> Device<'nvml> {
     }
 
     /**
-    Gets a bitmask of the supported throttle reasons.
+    Gets the current reasons that clocks are being held below their maximum.
 
-    These reasons can be returned by `.current_throttle_reasons()`.
+    This is the newer name NVML uses for `.current_throttle_reasons()`. Note
+    that multiple reasons can be affecting clocks at once.
+
+    The returned bitmask is created via the `ClocksEventReasons::from_bits_truncate`
+    method, meaning that any bits that don't correspond to flags present in this
+    version of the wrapper will be dropped.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all _fully supported_ devices.
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetCurrentClocksEventReasons")]
+    pub fn current_clocks_event_reasons(&self) -> Result<ClocksEventReasons, NvmlError> {
+        let sym = nvml_sym(
+            self.nvml
+                .lib
+                .nvmlDeviceGetCurrentClocksEventReasons
+                .as_ref(),
+        )?;
+
+        unsafe {
+            let mut reasons: c_ulonglong = mem::zeroed();
+
+            nvml_try(sym(self.device, &mut reasons))?;
+
+            Ok(ClocksEventReasons::from_bits_truncate(reasons))
+        }
+    }
+
+    /**
+    Gets a bitmask of the supported clocks event reasons.
+
+    These reasons can be returned by `.current_clocks_event_reasons()`.
+
+    The returned bitmask is created via the `ClocksEventReasons::from_bits_truncate`
+    method, meaning that any bits that don't correspond to flags present in this
+    version of the wrapper will be dropped.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all _fully supported_ devices.
+
+    # Environment Support
+
+    This method is not supported on virtual machines running vGPUs.
+    */
+    // Checked against local
+    // Tested
+    #[doc(alias = "nvmlDeviceGetSupportedClocksEventReasons")]
+    pub fn supported_clocks_event_reasons(&self) -> Result<ClocksEventReasons, NvmlError> {
+        let sym = nvml_sym(
+            self.nvml
+                .lib
+                .nvmlDeviceGetSupportedClocksEventReasons
+                .as_ref(),
+        )?;
+
+        unsafe {
+            let mut reasons: c_ulonglong = mem::zeroed();
+
+            nvml_try(sym(self.device, &mut reasons))?;
+
+            Ok(ClocksEventReasons::from_bits_truncate(reasons))
+        }
+    }
+
+    /**
+    Gets a bitmask of the supported throttle reasons.
+
+    These reasons can be returned by `.current_throttle_reasons()`.
 
     The returned bitmask is created via the `ThrottleReasons::from_bits_truncate`
     method, meaning that any bits that don't correspond to flags present in this
@@ -2939,6 +4637,9 @@ impl<'nvml> Device<'nvml> {
     Gets a `Vec` of possible graphics clocks that can be used as an arg for
     `set_applications_clocks()`.
 
+    The clocks are sorted in descending order (highest first) with duplicates
+    removed.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
@@ -2988,6 +4689,8 @@ impl<'nvml> Device<'nvml> {
         }
 
         items.truncate(count as usize);
+        items.sort_unstable_by(|a, b| b.cmp(a));
+        items.dedup();
         Ok(items)
     }
 
@@ -2995,6 +4698,9 @@ impl<'nvml> Device<'nvml> {
     Gets a `Vec` of possible memory clocks that can be used as an arg for
     `set_applications_clocks()`.
 
+    The clocks are sorted in descending order (highest first) with duplicates
+    removed.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
@@ -3038,6 +4744,8 @@ impl<'nvml> Device<'nvml> {
         }
 
         items.truncate(count as usize);
+        items.sort_unstable_by(|a, b| b.cmp(a));
+        items.dedup();
         Ok(items)
     }
 
@@ -3067,6 +4775,39 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets the current temperature readings for all of this `Device`'s sensors,
+    in °C.
+
+    The GPU die temperature is read via `.temperature()`. NVML doesn't offer a
+    `TemperatureSensor` for memory, so the memory temperature is read via
+    `.memory_temperature()` instead. Sensors that this `Device` does not have
+    are `None` in the returned `Temperatures`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+    */
+    pub fn temperatures(&self) -> Result<Temperatures, NvmlError> {
+        let gpu = match self.temperature(TemperatureSensor::Gpu) {
+            Ok(temp) => Some(temp),
+            Err(NvmlError::NotSupported) => None,
+            Err(e) => return Err(e),
+        };
+
+        let memory = match self.memory_temperature() {
+            Ok(temp) => Some(temp),
+            Err(NvmlError::NotSupported) | Err(NvmlError::FunctionNotFound) => None,
+            Err(e) => return Err(e),
+        };
+
+        Ok(Temperatures { gpu, memory })
+    }
+
     /**
     Gets the temperature threshold for this `Device` and the specified `threshold_type`, in °C.
 
@@ -3100,6 +4841,122 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets every temperature threshold for this `Device`, in °C.
+
+    Thresholds that this `Device` does not report are `None` in the returned
+    `TemperatureThresholds`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    // Tested
+    pub fn temperature_thresholds(&self) -> Result<TemperatureThresholds, NvmlError> {
+        let threshold = |threshold_type| optional(self.temperature_threshold(threshold_type));
+
+        Ok(TemperatureThresholds {
+            shutdown: threshold(TemperatureThreshold::Shutdown)?,
+            slowdown: threshold(TemperatureThreshold::Slowdown)?,
+            memory_max: threshold(TemperatureThreshold::MemoryMax)?,
+            gpu_max: threshold(TemperatureThreshold::GpuMax)?,
+            acoustic_min: threshold(TemperatureThreshold::AcousticMin)?,
+            acoustic_current: threshold(TemperatureThreshold::AcousticCurrent)?,
+            acoustic_max: threshold(TemperatureThreshold::AcousticMax)?,
+        })
+    }
+
+    /**
+    Gets the thermal sensor at the given index on this `Device`.
+
+    Use `.thermal_sensor_count()` to find out how many sensors there are, or
+    `.thermal_settings()` to get all of them at once.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid or there is no sensor at `index`
+    * `NotFound`, if NVML reports no sensor at `index`
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+    */
+    #[doc(alias = "nvmlDeviceGetThermalSettings")]
+    pub fn thermal_sensor(&self, index: u32) -> Result<ThermalSensor, NvmlError> {
+        self.raw_thermal_settings(index)?
+            .into_iter()
+            .next()
+            .ok_or(NvmlError::NotFound)?
+    }
+
+    /**
+    Gets the number of thermal sensors on this `Device`.
+
+    This is the number of sensors returned by `.thermal_settings()`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+    */
+    pub fn thermal_sensor_count(&self) -> Result<u32, NvmlError> {
+        Ok(self.thermal_settings()?.len() as u32)
+    }
+
+    /**
+    Gets all of the thermal sensors on this `Device`.
+
+    All sensors are read at once by passing `NVML_THERMAL_TARGET_ALL` as the
+    sensor index, so the caller doesn't need to know how many there are.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+    */
+    pub fn thermal_settings(&self) -> Result<Vec<ThermalSensor>, NvmlError> {
+        self.raw_thermal_settings(nvmlThermalTarget_t_NVML_THERMAL_TARGET_ALL as u32)?
+            .into_iter()
+            .collect()
+    }
+
+    // Helper for the above methods. Returns the first `count` sensors that
+    // NVML filled in.
+    fn raw_thermal_settings(
+        &self,
+        index: u32,
+    ) -> Result<Vec<Result<ThermalSensor, NvmlError>>, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetThermalSettings.as_ref())?;
+
+        unsafe {
+            let mut settings: nvmlGpuThermalSettings_t = mem::zeroed();
+            nvml_try(sym(self.device, index, &mut settings))?;
+
+            let count = (settings.count as usize).min(settings.sensor.len());
+
+            Ok(settings.sensor[..count]
+                .iter()
+                .map(|sensor| ThermalSensor::try_from(*sensor))
+                .collect())
+        }
+    }
+
     /**
     Gets the common ancestor for two devices.
 
@@ -3365,6 +5222,99 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Gets the driver, VBIOS and infoROM versions for this `Device` in one go.
+
+    The driver version is the same for every `Device` and is read via
+    `Nvml.sys_driver_version()`. Versions that this `Device` does not support
+    reporting (e.g. when it has no infoROM) are `None` in the returned
+    `VersionInfo`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Utf8Error`, if a string obtained from a C function is not valid UTF-8
+    * `Unknown`, on any unexpected error
+    */
+    // Tested
+    pub fn version_info(&self) -> Result<VersionInfo, NvmlError> {
+        Ok(VersionInfo {
+            driver: self.nvml.sys_driver_version()?,
+            vbios: optional(self.vbios_version())?,
+            info_rom_image: optional(self.info_rom_image_version())?,
+            info_rom_oem: optional(self.info_rom_version(InfoRom::OEM))?,
+            info_rom_ecc: optional(self.info_rom_version(InfoRom::ECC))?,
+            info_rom_power: optional(self.info_rom_version(InfoRom::Power))?,
+        })
+    }
+
+    /**
+    Gets the current clocks, performance state, utilization, temperature,
+    power usage and limit, and throttle reasons for this `Device` in one go.
+
+    Values that this `Device` does not support reporting are `None` in the
+    returned `PerformanceReport`. The values are read one after another, so they
+    are not all from exactly the same instant.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+    */
+    // Tested
+    pub fn performance_report(&self) -> Result<PerformanceReport, NvmlError> {
+        Ok(PerformanceReport {
+            graphics_clock: optional(self.clock_info(Clock::Graphics))?,
+            sm_clock: optional(self.clock_info(Clock::SM))?,
+            memory_clock: optional(self.clock_info(Clock::Memory))?,
+            performance_state: optional(self.performance_state())?,
+            utilization: optional(self.utilization_rates())?,
+            temperature: optional(self.temperature(TemperatureSensor::Gpu))?,
+            power_usage: optional(self.power_usage())?,
+            power_limit: optional(self.enforced_power_limit())?,
+            throttle_reasons: optional(self.current_throttle_reasons())?,
+        })
+    }
+
+    /**
+    Gets the current GPU, memory, encoder, and decoder utilization of this
+    `Device` in one go.
+
+    Encoder and decoder utilization are `None` in the returned
+    `FullUtilization` if this `Device` does not support reporting them. The
+    values are read one after another, so they are not all from exactly the
+    same instant. Use `.all_engine_utilization()` if you also need the JPEG
+    and OFA engines.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support reporting GPU and memory utilization
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Fermi and newer fully supported devices.
+    */
+    // Tested
+    pub fn full_utilization(&self) -> Result<FullUtilization, NvmlError> {
+        let rates = self.utilization_rates()?;
+
+        Ok(FullUtilization {
+            gpu: rates.gpu,
+            memory: rates.memory,
+            encoder: optional(self.encoder_utilization())?,
+            decoder: optional(self.decoder_utilization())?,
+        })
+    }
+
     /**
     Gets the duration of time during which this `Device` was throttled (lower than the
     requested clocks) due to power or thermal constraints.
@@ -3823,6 +5773,42 @@ impl<'nvml> Device<'nvml> {
         unsafe { nvml_try(sym(self.device)) }
     }
 
+    /**
+    Checks whether this `Device`'s infoROM is intact and gets its config
+    checksum.
+
+    This calls `.validate_info_rom()` and `.config_checksum()`, reporting a
+    corrupted infoROM in the returned `InforomHealth` instead of as a
+    `CorruptedInfoROM` error.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports all devices with an infoROM.
+    */
+    // Tested
+    pub fn inforom_health(&self) -> Result<InforomHealth, NvmlError> {
+        let valid = match self.validate_info_rom() {
+            Ok(()) => true,
+            Err(NvmlError::CorruptedInfoROM) => false,
+            Err(e) => return Err(e),
+        };
+
+        let checksum = match self.config_checksum() {
+            Ok(checksum) => Some(checksum),
+            Err(NvmlError::CorruptedInfoROM) | Err(NvmlError::NotSupported) => None,
+            Err(e) => return Err(e),
+        };
+
+        Ok(InforomHealth { valid, checksum })
+    }
+
     // Wrappers for things from Accounting Statistics now
 
     /**
@@ -4053,6 +6039,39 @@ impl<'nvml> Device<'nvml> {
         unsafe { nvml_try(sym(self.device, state_from_bool(enabled))) }
     }
 
+    /**
+    Enables per-process accounting if it isn't already enabled.
+
+    Returns `true` if accounting was enabled by this call and `false` if it was
+    already on. Unlike calling `.set_accounting(true)` directly, this never
+    touches the accounting mode when it's already enabled, so existing
+    accounting information is left alone.
+
+    Requires root/admin permissions if accounting needs to be enabled.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    // Tested (no-run)
+    pub fn ensure_accounting_enabled(&mut self) -> Result<bool, NvmlError> {
+        if self.is_accounting_enabled()? {
+            return Ok(false);
+        }
+
+        self.set_accounting(true)?;
+        Ok(true)
+    }
+
     // Device commands starting here
 
     /**
@@ -4089,17 +6108,50 @@ impl<'nvml> Device<'nvml> {
     }
 
     /**
-    Changes the root/admin restrictions on certain APIs.
+    Clears both the `EccCounter::Volatile` and `EccCounter::Aggregate` ECC
+    error counts for this `Device`.
 
-    This method can be used by a root/admin user to give non root/admin users access
-    to certain otherwise-restricted APIs. The new setting lasts for the lifetime of
-    the NVIDIA driver; it is not persistent. See `.is_api_restricted()` to query
-    current settings.
+    This calls `.clear_ecc_error_counts()` once for each counter type. Both
+    calls are always made; if either fails, the first error encountered is
+    returned.
+
+    Requires root/admin permissions and ECC mode to be enabled.
 
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
-    * `InvalidArg`, if the `Device` is invalid or `api_type` is invalid (shouldn't occur?)
+    * `InvalidArg`, if the `Device` is invalid
+    * `NotSupported`, if this `Device` does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices. Only applicable to devices with
+    ECC. Requires `InfoRom::ECC` version 2.0 or higher to clear aggregate
+    location-based ECC counts.
+    */
+    // Tested (no-run)
+    pub fn clear_all_ecc_error_counts(&mut self) -> Result<(), NvmlError> {
+        let volatile = self.clear_ecc_error_counts(EccCounter::Volatile);
+        let aggregate = self.clear_ecc_error_counts(EccCounter::Aggregate);
+
+        volatile.and(aggregate)
+    }
+
+    /**
+    Changes the root/admin restrictions on certain APIs.
+
+    This method can be used by a root/admin user to give non root/admin users access
+    to certain otherwise-restricted APIs. The new setting lasts for the lifetime of
+    the NVIDIA driver; it is not persistent. See `.is_api_restricted()` to query
+    current settings.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid or `api_type` is invalid (shouldn't occur?)
     * `NotSupported`, if this `Device` does not support changing API restrictions or
     this `Device` does not support the feature that API restrictions are being set for
     (e.g. enabling/disabling auto boosted clocks is not supported by this `Device`).
@@ -4126,6 +6178,40 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
+    /**
+    Changes the root/admin restrictions on every API that can be restricted
+    (`Api::ApplicationClocks` and `Api::AutoBoostedClocks`) at once.
+
+    This calls `.set_api_restricted()` for each `Api` variant. APIs that this
+    `Device` does not support restricting are skipped.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    The restrictions on any APIs handled before an error occurred will already
+    have been changed.
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    // Tested (no-run)
+    pub fn set_all_api_restrictions(&mut self, restricted: bool) -> Result<(), NvmlError> {
+        for api_type in [Api::ApplicationClocks, Api::AutoBoostedClocks] {
+            match self.set_api_restricted(api_type, restricted) {
+                Ok(()) | Err(NvmlError::NotSupported) => (),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+
     /**
     Sets clocks that applications will lock to.
 
@@ -4177,6 +6263,60 @@ impl<'nvml> Device<'nvml> {
         unsafe { nvml_try(sym(self.device, mem_clock, graphics_clock)) }
     }
 
+    /**
+    Sets clocks that applications will lock to, after validating them against
+    the lists of supported clocks.
+
+    This behaves like [`Self::set_applications_clocks()`] but first checks that
+    `mem_clock` is present in [`Self::supported_memory_clocks()`] and that
+    `graphics_clock` is present in [`Self::supported_graphics_clocks()`] for
+    that memory clock. If either check fails, NVML is never called and the
+    closest supported combination is returned in the error instead.
+
+    # Errors
+
+    * `UnsupportedClocks`, if the clocks are not a supported combination
+    * `InvalidArg`, if this `Device` is invalid or reports no supported clocks
+    * `NotSupported`, if this `Device` does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer non-GeForce fully supported devices and Maxwell or newer
+    GeForce devices.
+    */
+    #[doc(alias = "nvmlDeviceSetApplicationsClocks")]
+    pub fn set_applications_clocks_checked(
+        &mut self,
+        mem_clock: u32,
+        graphics_clock: u32,
+    ) -> Result<(), NvmlError> {
+        fn nearest(clocks: &[u32], target: u32) -> Option<u32> {
+            clocks
+                .iter()
+                .copied()
+                .min_by_key(|c| (i64::from(*c) - i64::from(target)).abs())
+        }
+
+        let mem_clocks = self.supported_memory_clocks()?;
+        let nearest_mem_clock = nearest(&mem_clocks, mem_clock).ok_or(NvmlError::InvalidArg)?;
+
+        let graphics_clocks = self.supported_graphics_clocks(nearest_mem_clock)?;
+        let nearest_graphics_clock =
+            nearest(&graphics_clocks, graphics_clock).ok_or(NvmlError::InvalidArg)?;
+
+        if nearest_mem_clock != mem_clock || nearest_graphics_clock != graphics_clock {
+            return Err(NvmlError::UnsupportedClocks {
+                nearest_mem_clock,
+                nearest_graphics_clock,
+            });
+        }
+
+        self.set_applications_clocks(mem_clock, graphics_clock)
+    }
+
     /**
     Sets the compute mode for this `Device`.
 
@@ -4371,6 +6511,77 @@ impl<'nvml> Device<'nvml> {
         unsafe { nvml_try(sym(self.device, min_clock_mhz, max_clock_mhz)) }
     }
 
+    /**
+    Gets the range of memory clocks, in MHz, that this `Device` supports, as
+    `(min, max)`.
+
+    NVML doesn't expose an explicit range for memory clock locking, so this is
+    derived from [`Self::supported_memory_clocks()`].
+
+    # Errors
+
+    * `InvalidArg`, if this `Device` is invalid or reports no supported clocks
+    * `NotSupported`, if this `Device` does not support this feature
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    pub fn supported_memory_clock_range(&self) -> Result<(u32, u32), NvmlError> {
+        // Sorted in descending order
+        let clocks = self.supported_memory_clocks()?;
+
+        match (clocks.last(), clocks.first()) {
+            (Some(&min), Some(&max)) => Ok((min, max)),
+            _ => Err(NvmlError::InvalidArg),
+        }
+    }
+
+    /**
+    Lock this [`Device`]'s memory clocks to a specific frequency range, after
+    validating the range against [`Self::supported_memory_clock_range()`].
+
+    This behaves like [`Self::set_mem_locked_clocks()`] but checks the range
+    first. If the check fails, NVML is never called and the supported range is
+    returned in the error instead.
+
+    # Errors
+
+    * `ClocksOutOfRange`, if either clock is outside of the supported range
+    * `InvalidArg`, if `min_clock_mhz` is greater than `max_clock_mhz`, this `Device` is invalid or it reports no supported clocks
+    * `NotSupported`, if this `Device` does not support this feature
+    * `NoPermission`, if the user doesn't have permission to perform this operation
+    * `GpuLost`, if this `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Ampere and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceSetMemoryLockedClocks")]
+    pub fn set_mem_locked_clocks_checked(
+        &mut self,
+        min_clock_mhz: u32,
+        max_clock_mhz: u32,
+    ) -> Result<(), NvmlError> {
+        if min_clock_mhz > max_clock_mhz {
+            return Err(NvmlError::InvalidArg);
+        }
+
+        let (min_supported, max_supported) = self.supported_memory_clock_range()?;
+
+        if min_clock_mhz < min_supported || max_clock_mhz > max_supported {
+            return Err(NvmlError::ClocksOutOfRange {
+                min_supported,
+                max_supported,
+            });
+        }
+
+        self.set_mem_locked_clocks(min_clock_mhz, max_clock_mhz)
+    }
+
     /**
     Reset this [`Device`]'s memory clocks to their default values.
 
@@ -4433,6 +6644,10 @@ impl<'nvml> Device<'nvml> {
     Requires root/admin permissions. Changing GOMs requires a reboot, a requirement
     that may be removed in the future.
 
+    A successful return only means that the new mode is now pending. Use
+    `.gpu_operation_mode()` or `.gpu_operation_mode_pending_differs()` to check
+    whether a reboot is still required for it to take effect.
+
     Compute only GOMs don't support graphics acceleration. Under Windows switching
     to these GOMs when the pending driver model is WDDM (physical display attached)
     is not supported.
@@ -4543,11 +6758,9 @@ impl<'nvml> Device<'nvml> {
     Use `.supported_event_types()` to find out which events you can register for
     this `Device`.
 
-    **Unfortunately, due to the way `error-chain` works, there is no way to
-    return the set if it is still valid after an error has occured with the
-    register call.** The set that you passed in will be freed if any error
-    occurs and will not be returned to you. This is not desired behavior
-    and I will fix it as soon as it is possible to do so.
+    If an error occurs and the set that you passed in is still valid, it is
+    returned to you in the `set` field of the `RegisterEventsError` so that you
+    can use it again.
 
     All events that occurred before this call was made will not be recorded.
 
@@ -4564,6 +6777,7 @@ impl<'nvml> Device<'nvml> {
     * `Unknown`, on any unexpected error. **If this error is returned, the `set` you
     passed in has had its resources freed and will not be returned to you**. NVIDIA's
     docs say that this error means that the set is in an invalid state.
+    * `SetReleaseFailed`, if freeing the set after an `Unknown` error failed
 
     # Device Support
 
@@ -4611,8 +6825,17 @@ impl<'nvml> Device<'nvml> {
         &self,
         events: EventTypes,
         set: EventSet<'nvml>,
-    ) -> Result<EventSet<'nvml>, NvmlErrorWithSource> {
-        let sym = nvml_sym(self.nvml.lib.nvmlDeviceRegisterEvents.as_ref())?;
+    ) -> Result<EventSet<'nvml>, RegisterEventsError<'nvml>> {
+        let sym = match nvml_sym(self.nvml.lib.nvmlDeviceRegisterEvents.as_ref()) {
+            Ok(sym) => sym,
+            Err(error) => {
+                return Err(RegisterEventsError {
+                    error,
+                    source: None,
+                    set: Some(set),
+                })
+            }
+        };
 
         unsafe {
             match nvml_try(sym(self.device, events.bits(), set.handle())) {
@@ -4621,25 +6844,20 @@ impl<'nvml> Device<'nvml> {
                     // NVIDIA says that if an Unknown error is returned, `set` will
                     // be in an undefined state and should be freed.
                     if let Err(e) = set.release_events() {
-                        return Err(NvmlErrorWithSource {
+                        return Err(RegisterEventsError {
                             error: NvmlError::SetReleaseFailed,
                             source: Some(e),
+                            set: None,
                         });
                     }
 
                     Err(NvmlError::Unknown.into())
                 }
-                Err(e) => {
-                    // TODO: return set here so you can use it again?
-                    if let Err(e) = set.release_events() {
-                        return Err(NvmlErrorWithSource {
-                            error: NvmlError::SetReleaseFailed,
-                            source: Some(e),
-                        });
-                    }
-
-                    Err(e.into())
-                }
+                Err(error) => Err(RegisterEventsError {
+                    error,
+                    source: None,
+                    set: Some(set),
+                }),
             }
         }
     }
@@ -5047,119 +7265,850 @@ impl<'nvml> Device<'nvml> {
         }
     }
 
-    // NvLink
+    // Diagnostics
 
     /**
-    Obtain a struct that represents an NvLink.
+    Calls every read-only, argument-free query on this `Device` and records
+    whether each one succeeded.
+
+    Queries that take an argument are made with a sensible default (e.g. fan 0,
+    the graphics clock, the GPU temperature sensor). Nothing is returned from
+    the individual queries beyond success or the kind of error encountered,
+    making the report suitable for attaching to bug reports.
+
+    This method itself cannot fail; errors are recorded in the report.
+    */
+    pub fn capability_report(&self) -> CapabilityReport {
+        let mut entries = vec![];
+        let mut record = |method: &str, result: Result<(), NvmlError>| {
+            entries.push(CapabilityEntry {
+                method: method.into(),
+                result: result.map_err(|e| error_kind(&e)),
+            })
+        };
 
-    NVIDIA does not provide any information as to how to obtain a valid NvLink
-    value, so you're on your own there.
-    */
-    pub fn link_wrapper_for(&self, link: u32) -> NvLink {
-        NvLink { device: self, link }
-    }
-}
+        record("architecture", self.architecture().map(|_| ()));
+        record(
+            "auto_boosted_clocks_enabled",
+            self.auto_boosted_clocks_enabled().map(|_| ()),
+        );
+        record("bar1_memory_info", self.bar1_memory_info().map(|_| ()));
+        record("board_id", self.board_id().map(|_| ()));
+        record("board_part_number", self.board_part_number().map(|_| ()));
+        record("brand", self.brand().map(|_| ()));
+        record("bridge_chip_info", self.bridge_chip_info().map(|_| ()));
+        record("bus_type", self.bus_type().map(|_| ()));
+        record("clock_info", self.clock_info(Clock::Graphics).map(|_| ()));
+        record("compute_mode", self.compute_mode().map(|_| ()));
+        record("config_checksum", self.config_checksum().map(|_| ()));
+        record(
+            "cuda_compute_capability",
+            self.cuda_compute_capability().map(|_| ()),
+        );
+        record(
+            "current_pcie_link_gen",
+            self.current_pcie_link_gen().map(|_| ()),
+        );
+        record(
+            "current_pcie_link_width",
+            self.current_pcie_link_width().map(|_| ()),
+        );
+        record(
+            "current_throttle_reasons",
+            self.current_throttle_reasons().map(|_| ()),
+        );
+        record(
+            "decoder_utilization",
+            self.decoder_utilization().map(|_| ()),
+        );
+        #[cfg(target_os = "windows")]
+        record("driver_model", self.driver_model().map(|_| ()));
+        record("encoder_stats", self.encoder_stats().map(|_| ()));
+        record(
+            "encoder_utilization",
+            self.encoder_utilization().map(|_| ()),
+        );
+        record(
+            "enforced_power_limit",
+            self.enforced_power_limit().map(|_| ()),
+        );
+        record("fan_speed", self.fan_speed(0).map(|_| ()));
+        record("fbc_stats", self.fbc_stats().map(|_| ()));
+        record("gpu_operation_mode", self.gpu_operation_mode().map(|_| ()));
+        record("index", self.index().map(|_| ()));
+        record(
+            "info_rom_image_version",
+            self.info_rom_image_version().map(|_| ()),
+        );
+        record("irq_num", self.irq_num().map(|_| ()));
+        record(
+            "is_accounting_enabled",
+            self.is_accounting_enabled().map(|_| ()),
+        );
+        record("is_display_active", self.is_display_active().map(|_| ()));
+        record(
+            "is_display_connected",
+            self.is_display_connected().map(|_| ()),
+        );
+        record("is_ecc_enabled", self.is_ecc_enabled().map(|_| ()));
+        #[cfg(target_os = "linux")]
+        record(
+            "is_in_persistent_mode",
+            self.is_in_persistent_mode().map(|_| ()),
+        );
+        record("is_multi_gpu_board", self.is_multi_gpu_board().map(|_| ()));
+        record(
+            "max_clock_info",
+            self.max_clock_info(Clock::Graphics).map(|_| ()),
+        );
+        record("max_pcie_link_gen", self.max_pcie_link_gen().map(|_| ()));
+        record(
+            "max_pcie_link_speed",
+            self.max_pcie_link_speed().map(|_| ()),
+        );
+        record(
+            "max_pcie_link_width",
+            self.max_pcie_link_width().map(|_| ()),
+        );
+        record("memory_bus_width", self.memory_bus_width().map(|_| ()));
+        record("memory_info", self.memory_info().map(|_| ()));
+        #[cfg(target_os = "linux")]
+        record("minor_number", self.minor_number().map(|_| ()));
+        record("name", self.name().map(|_| ()));
+        record("num_cores", self.num_cores().map(|_| ()));
+        record("num_fans", self.num_fans().map(|_| ()));
+        record("pci_info", self.pci_info().map(|_| ()));
+        record("pcie_link_speed", self.pcie_link_speed().map(|_| ()));
+        record(
+            "pcie_replay_counter",
+            self.pcie_replay_counter().map(|_| ()),
+        );
+        record("performance_state", self.performance_state().map(|_| ()));
+        record(
+            "power_management_limit",
+            self.power_management_limit().map(|_| ()),
+        );
+        record("power_source", self.power_source().map(|_| ()));
+        record("power_usage", self.power_usage().map(|_| ()));
+        record(
+            "running_compute_processes",
+            self.running_compute_processes().map(|_| ()),
+        );
+        record(
+            "running_graphics_processes",
+            self.running_graphics_processes().map(|_| ()),
+        );
+        record("serial", self.serial().map(|_| ()));
+        #[cfg(target_os = "linux")]
+        record(
+            "supported_event_types",
+            self.supported_event_types().map(|_| ()),
+        );
+        record(
+            "supported_memory_clocks",
+            self.supported_memory_clocks().map(|_| ()),
+        );
+        record(
+            "supported_throttle_reasons",
+            self.supported_throttle_reasons().map(|_| ()),
+        );
+        record(
+            "temperature",
+            self.temperature(TemperatureSensor::Gpu).map(|_| ()),
+        );
+        record(
+            "total_energy_consumption",
+            self.total_energy_consumption().map(|_| ()),
+        );
+        record("utilization_rates", self.utilization_rates().map(|_| ()));
+        record("uuid", self.uuid().map(|_| ()));
+        record("vbios_version", self.vbios_version().map(|_| ()));
+
+        CapabilityReport { entries }
+    }
+
+    // vGPU
 
-#[cfg(test)]
-#[deny(unused_mut)]
-mod test {
-    #[cfg(target_os = "linux")]
-    use crate::bitmasks::event::*;
-    #[cfg(target_os = "windows")]
-    use crate::bitmasks::Behavior;
-    use crate::enum_wrappers::device::*;
-    use crate::enums::device::GpuLockedClocksSetting;
-    use crate::error::*;
-    use crate::structs::device::FieldId;
-    use crate::sys_exports::field_id::*;
-    use crate::test_utils::*;
+    /**
+    Gets the vGPU types supported by this `Device`.
 
-    // This modifies device state, so we don't want to actually run the test
-    #[allow(dead_code)]
-    #[cfg(target_os = "linux")]
-    fn clear_cpu_affinity() {
-        let nvml = nvml();
-        let mut device = device(&nvml);
+    See `VgpuTypeId` for the information that can be queried about each type.
 
-        device.clear_cpu_affinity().unwrap();
-    }
+    # Errors
 
-    #[test]
-    #[ignore = "my machine does not support this call"]
-    fn is_api_restricted() {
-        let nvml = nvml();
-        test_with_device(3, &nvml, |device| {
-            device.is_api_restricted(Api::ApplicationClocks)?;
-            device.is_api_restricted(Api::AutoBoostedClocks)
-        })
-    }
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support vGPUs
+    * `Unknown`, on any unexpected error
 
-    #[test]
-    #[ignore = "my machine does not support this call"]
-    fn applications_clock() {
-        let nvml = nvml();
-        test_with_device(3, &nvml, |device| {
-            let gfx_clock = device.applications_clock(Clock::Graphics)?;
-            let sm_clock = device.applications_clock(Clock::SM)?;
-            let mem_clock = device.applications_clock(Clock::Memory)?;
-            let vid_clock = device.applications_clock(Clock::Video)?;
+    # Device Support
 
-            Ok(format!(
-                "Graphics Clock: {}, SM Clock: {}, Memory Clock: {}, Video Clock: {}",
-                gfx_clock, sm_clock, mem_clock, vid_clock
-            ))
-        })
-    }
+    Supports Kepler and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetSupportedVgpus")]
+    pub fn supported_vgpus(&self) -> Result<Vec<VgpuTypeId<'_, 'nvml>>, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetSupportedVgpus.as_ref())?;
 
-    #[test]
-    #[ignore = "my machine does not support this call"]
-    fn auto_boosted_clocks_enabled() {
-        let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.auto_boosted_clocks_enabled())
-    }
+        unsafe {
+            let mut count = match self.supported_vgpus_count()? {
+                0 => return Ok(vec![]),
+                value => value,
+            };
+            let mut ids: Vec<nvmlVgpuTypeId_t> = vec![mem::zeroed(); count as usize];
 
-    #[test]
-    fn bar1_memory_info() {
-        let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.bar1_memory_info())
-    }
+            nvml_try(sym(self.device, &mut count, ids.as_mut_ptr()))?;
 
-    #[test]
-    fn board_id() {
-        let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.board_id())
+            Ok(ids
+                .into_iter()
+                .take(count as usize)
+                .map(|id| VgpuTypeId { device: self, id })
+                .collect())
+        }
     }
 
-    #[test]
-    fn brand() {
-        let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.brand())
-    }
+    // Helper function for the above.
+    fn supported_vgpus_count(&self) -> Result<c_uint, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetSupportedVgpus.as_ref())?;
 
-    #[test]
-    #[ignore = "my machine does not support this call"]
-    fn bridge_chip_info() {
-        let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.bridge_chip_info())
-    }
+        unsafe {
+            // Indicates that we want the count
+            let mut count: c_uint = 0;
 
-    #[test]
-    #[ignore = "my machine does not support this call"]
-    fn clock() {
-        let nvml = nvml();
-        test_with_device(3, &nvml, |device| {
-            device.clock(Clock::Graphics, ClockId::Current)?;
-            device.clock(Clock::SM, ClockId::TargetAppClock)?;
-            device.clock(Clock::Memory, ClockId::DefaultAppClock)?;
-            device.clock(Clock::Video, ClockId::TargetAppClock)
-            // My machine does not support CustomerMaxBoost
-        })
+            // Null also indicates that we want the count
+            match sym(self.device, &mut count, ptr::null_mut()) {
+                // List is empty
+                nvmlReturn_enum_NVML_SUCCESS => Ok(0),
+                // Count is set to vGPU type count
+                nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => Ok(count),
+                // We know this is an error
+                other => nvml_try(other).map(|_| 0),
+            }
+        }
     }
 
-    #[test]
-    #[ignore = "my machine does not support this call"]
-    fn max_customer_boost_clock() {
-        let nvml = nvml();
+    /**
+    Gets the vGPU types that can currently be created on this `Device`.
+
+    This list changes as vGPU instances are created and destroyed; the types
+    that remain creatable depend on the types already running.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support vGPUs
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetCreatableVgpus")]
+    pub fn creatable_vgpus(&self) -> Result<Vec<VgpuTypeId<'_, 'nvml>>, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetCreatableVgpus.as_ref())?;
+
+        unsafe {
+            let mut count = match self.creatable_vgpus_count()? {
+                0 => return Ok(vec![]),
+                value => value,
+            };
+            let mut ids: Vec<nvmlVgpuTypeId_t> = vec![mem::zeroed(); count as usize];
+
+            nvml_try(sym(self.device, &mut count, ids.as_mut_ptr()))?;
+
+            Ok(ids
+                .into_iter()
+                .take(count as usize)
+                .map(|id| VgpuTypeId { device: self, id })
+                .collect())
+        }
+    }
+
+    // Helper function for the above.
+    fn creatable_vgpus_count(&self) -> Result<c_uint, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetCreatableVgpus.as_ref())?;
+
+        unsafe {
+            // Indicates that we want the count
+            let mut count: c_uint = 0;
+
+            // Null also indicates that we want the count
+            match sym(self.device, &mut count, ptr::null_mut()) {
+                // List is empty
+                nvmlReturn_enum_NVML_SUCCESS => Ok(0),
+                // Count is set to vGPU type count
+                nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => Ok(count),
+                // We know this is an error
+                other => nvml_try(other).map(|_| 0),
+            }
+        }
+    }
+
+    /**
+    Gets the vGPU instances currently running on this `Device`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support vGPUs
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetActiveVgpus")]
+    pub fn active_vgpus(&self) -> Result<Vec<VgpuInstance<'_, 'nvml>>, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetActiveVgpus.as_ref())?;
+
+        unsafe {
+            let mut count = match self.active_vgpus_count()? {
+                0 => return Ok(vec![]),
+                value => value,
+            };
+            let mut instances: Vec<nvmlVgpuInstance_t> = vec![mem::zeroed(); count as usize];
+
+            nvml_try(sym(self.device, &mut count, instances.as_mut_ptr()))?;
+
+            Ok(instances
+                .into_iter()
+                .take(count as usize)
+                .map(|instance| VgpuInstance {
+                    device: self,
+                    instance,
+                })
+                .collect())
+        }
+    }
+
+    // Helper function for the above.
+    fn active_vgpus_count(&self) -> Result<c_uint, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetActiveVgpus.as_ref())?;
+
+        unsafe {
+            // Indicates that we want the count
+            let mut count: c_uint = 0;
+
+            // Null also indicates that we want the count
+            match sym(self.device, &mut count, ptr::null_mut()) {
+                // List is empty
+                nvmlReturn_enum_NVML_SUCCESS => Ok(0),
+                // Count is set to vGPU instance count
+                nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => Ok(count),
+                // We know this is an error
+                other => nvml_try(other).map(|_| 0),
+            }
+        }
+    }
+
+    /**
+    Gets the vGPU metadata for this `Device`.
+
+    The metadata can be passed to `Nvml.vgpu_compatibility()` along with a
+    vGPU instance's metadata to check whether the vGPU can be migrated to this
+    `Device`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support vGPUs
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetVgpuMetadata")]
+    pub fn vgpu_metadata(&self) -> Result<VgpuPgpuMetadata, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetVgpuMetadata.as_ref())?;
+
+        unsafe {
+            let buffer = metadata_buffer(|buf, size| sym(self.device, buf, size))?;
+
+            Ok(VgpuPgpuMetadata::from_buffer(&buffer))
+        }
+    }
+
+    /**
+    Gets whether or not vGPUs running on this `Device` can be live-migrated.
+
+    This reads the virtualization capabilities from this `Device`'s vGPU
+    metadata. See `VgpuInstance.migration_capability()` for the equivalent
+    check on a running vGPU.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support vGPUs
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell and newer fully supported devices.
+    */
+    pub fn is_migration_capable(&self) -> Result<bool, NvmlError> {
+        Ok(self.vgpu_metadata()?.is_migration_capable())
+    }
+
+    /**
+    Gets whether or not this `Device` has the given vGPU capability.
+
+    NVML reports capabilities as integers; any non-zero value is interpreted as
+    `true`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this `Device` is invalid
+    * `NotSupported`, if this `Device` does not support vGPUs
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlDeviceGetVgpuCapabilities")]
+    pub fn vgpu_capability(&self, capability: DeviceVgpuCapability) -> Result<bool, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlDeviceGetVgpuCapabilities.as_ref())?;
+
+        unsafe {
+            let mut result: c_uint = mem::zeroed();
+            nvml_try(sym(self.device, capability.as_c(), &mut result))?;
+
+            #[allow(clippy::match_like_matches_macro)]
+            Ok(match result {
+                0 => false,
+                // Not worth an error or a panic if the value is > 1
+                _ => true,
+            })
+        }
+    }
+
+    // NvLink
+
+    /**
+    Obtain a struct that represents an NvLink.
+
+    NVIDIA does not provide any information as to how to obtain a valid NvLink
+    value, so you're on your own there.
+    */
+    pub fn link_wrapper_for(&self, link: u32) -> NvLink {
+        NvLink { device: self, link }
+    }
+
+    /**
+    Gets the remote endpoint of each of this `Device`'s active NvLinks.
+
+    Each entry contains the link index, the PCI information of the device on
+    the other end of the link, and what type of device that is. Together these
+    describe how this `Device` is wired up to other GPUs, NvSwitches, and NPUs.
+
+    Links are found by probing link indices up to `NVML_NVLINK_MAX_LINKS`;
+    indices that NVML reports as `InvalidArg` and inactive links are skipped.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Pascal or newer fully supported devices.
+    */
+    pub fn nvlink_remote_endpoints(
+        &self,
+    ) -> Result<Vec<(u32, PciInfo, RemoteDeviceType)>, NvmlError> {
+        let mut endpoints = Vec::new();
+
+        for index in 0..NVML_NVLINK_MAX_LINKS {
+            let link = self.link_wrapper_for(index);
+
+            match link.is_active() {
+                Ok(true) => (),
+                Ok(false) | Err(NvmlError::InvalidArg) => continue,
+                Err(e) => return Err(e),
+            }
+
+            endpoints.push((index, link.remote_pci_info()?, link.remote_device_type()?));
+        }
+
+        Ok(endpoints)
+    }
+
+    /**
+    Gets a rollup of the state of all of this `Device`'s NvLinks.
+
+    Links are found by probing link indices up to `NVML_NVLINK_MAX_LINKS`;
+    indices that NVML reports as `InvalidArg` are skipped. Versions are only
+    read from active links. Error counters that a link does not support are
+    left out of the totals.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Pascal or newer fully supported devices.
+    */
+    pub fn nvlink_summary(&self) -> Result<NvLinkSummary, NvmlError> {
+        let mut link_count = 0;
+        let mut active_count = 0;
+        let mut versions = Vec::new();
+        let mut errors = [0u64; 4];
+
+        for index in 0..NVML_NVLINK_MAX_LINKS {
+            let link = self.link_wrapper_for(index);
+
+            let is_active = match link.is_active() {
+                Ok(is_active) => is_active,
+                Err(NvmlError::InvalidArg) => continue,
+                Err(e) => return Err(e),
+            };
+
+            link_count += 1;
+
+            if is_active {
+                active_count += 1;
+                versions.push(link.version()?);
+            }
+
+            let counters = [
+                ErrorCounter::DlReplay,
+                ErrorCounter::DlRecovery,
+                ErrorCounter::DlCrcFlit,
+                ErrorCounter::DlCrcData,
+            ];
+
+            for (total, counter) in errors.iter_mut().zip(counters) {
+                match link.error_counter(counter) {
+                    Ok(count) => *total += count,
+                    Err(NvmlError::NotSupported) => (),
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        Ok(NvLinkSummary {
+            link_count,
+            active_count,
+            version: common_version(&versions),
+            dl_replay_errors: errors[0],
+            dl_recovery_errors: errors[1],
+            dl_crc_flit_errors: errors[2],
+            dl_crc_data_errors: errors[3],
+        })
+    }
+
+    /**
+    Gets the NvLink version shared by all of this `Device`'s active links.
+
+    Returns `None` if the active links report different versions, which
+    usually points to a misconfiguration, or if no links are active.
+
+    Links are found by probing link indices up to `NVML_NVLINK_MAX_LINKS`;
+    indices that NVML reports as `InvalidArg` and inactive links are skipped.
+    Use `.nvlink_summary()` if you also want link counts and error totals.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Pascal or newer fully supported devices.
+    */
+    pub fn nvlink_common_version(&self) -> Result<Option<u32>, NvmlError> {
+        let mut versions = Vec::new();
+
+        for index in 0..NVML_NVLINK_MAX_LINKS {
+            let link = self.link_wrapper_for(index);
+
+            match link.is_active() {
+                Ok(true) => versions.push(link.version()?),
+                Ok(false) | Err(NvmlError::InvalidArg) => (),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(common_version(&versions))
+    }
+}
+
+// The version every link in `versions` agrees on, or `None` if they differ or
+// there are none.
+fn common_version(versions: &[u32]) -> Option<u32> {
+    match versions.first() {
+        Some(&first) if versions.iter().all(|&v| v == first) => Some(first),
+        _ => None,
+    }
+}
+
+/// Converts a PCI bus ID as reported by NVML (e.g. `00000000:3B:00.0`) into
+/// the form used for sysfs paths (e.g. `0000:3b:00.0`).
+#[cfg(target_os = "linux")]
+fn sysfs_pci_address(bus_id: &str) -> Option<String> {
+    let normalized = crate::normalize_pci_bus_id(bus_id)?;
+    let (domain, rest) = normalized.split_once(':')?;
+    let domain = u32::from_str_radix(domain, 16).ok()?;
+
+    Some(format!("{:04x}:{}", domain, rest.to_ascii_lowercase()))
+}
+
+/// Parses the contents of a sysfs `numa_node` file, where `-1` means that
+/// there is no NUMA node.
+#[cfg(target_os = "linux")]
+fn parse_numa_node(contents: &str) -> Result<Option<i32>, NvmlError> {
+    match contents.trim().parse::<i32>() {
+        Ok(node) if node >= 0 => Ok(Some(node)),
+        Ok(_) => Ok(None),
+        Err(_) => Err(NvmlError::Unknown),
+    }
+}
+
+/// Returns the name of the given error's variant (e.g. `"NotSupported"`),
+/// without any data that it carries.
+fn error_kind(error: &NvmlError) -> String {
+    let debug = format!("{:?}", error);
+
+    debug
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .next()
+        .unwrap_or_default()
+        .into()
+}
+
+/// Converts a power reading in milliwatts to watts.
+fn milliwatts_to_watts(milliwatts: u32) -> f64 {
+    f64::from(milliwatts) / 1000.0
+}
+
+/// Returns `numerator / denominator`, or 0.0 if `denominator` is 0.
+fn fraction(numerator: u32, denominator: u32) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        f64::from(numerator) / f64::from(denominator)
+    }
+}
+
+/// Maps a `NotSupported` error to `Ok(None)`, for values that not every
+/// `Device` can report.
+fn optional<T>(res: Result<T, NvmlError>) -> Result<Option<T>, NvmlError> {
+    match res {
+        Ok(value) => Ok(Some(value)),
+        Err(NvmlError::NotSupported) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Returns the most recent of the given samples that belongs to `pid`.
+fn latest_sample_for_pid(
+    samples: Vec<ProcessUtilizationSample>,
+    pid: u32,
+) -> Option<ProcessUtilizationSample> {
+    samples
+        .into_iter()
+        .filter(|s| s.pid == pid)
+        .max_by_key(|s| s.timestamp)
+}
+
+/// Maximum number of times `retry_with_required_size()` will retry a call.
+const MAX_INSUFFICIENT_SIZE_RETRIES: usize = 4;
+
+/// Calls `query` with `size`, retrying with the required size whenever it
+/// returns `InsufficientSize(Some(_))`.
+///
+/// The number of retries is bounded so that a buffer that keeps growing (e.g.
+/// on a GPU with lots of process churn) can't cause an infinite loop.
+pub(crate) fn retry_with_required_size<T, F>(mut size: usize, mut query: F) -> Result<T, NvmlError>
+where
+    F: FnMut(usize) -> Result<T, NvmlError>,
+{
+    for _ in 0..MAX_INSUFFICIENT_SIZE_RETRIES {
+        match query(size) {
+            Err(NvmlError::InsufficientSize(Some(required))) => size = required.max(size),
+            value => return value,
+        }
+    }
+
+    query(size)
+}
+
+/// Percentage below the max clock that a clock must fall to be considered
+/// throttled by `Device::is_clock_throttled()`.
+const CLOCK_THROTTLE_TOLERANCE_PERCENT: u64 = 5;
+
+/// Returns `true` if `current` is more than `CLOCK_THROTTLE_TOLERANCE_PERCENT`
+/// below `max`.
+fn clock_below_max(current: u32, max: u32) -> bool {
+    u64::from(current) * 100 < u64::from(max) * (100 - CLOCK_THROTTLE_TOLERANCE_PERCENT)
+}
+
+/// Returns `true` if `current` is above the `default` applications clock.
+fn clock_above_default(current: u32, default: u32) -> bool {
+    current > default
+}
+
+/// Sums the memory used by the given processes, counting each pid only once and
+/// skipping processes whose usage is unavailable.
+fn sum_used_gpu_memory<'a, I>(processes: I) -> u64
+where
+    I: IntoIterator<Item = &'a ProcessInfo>,
+{
+    let mut seen = HashSet::new();
+
+    processes
+        .into_iter()
+        .filter(|p| seen.insert(p.pid))
+        .filter_map(|p| match p.used_gpu_memory {
+            UsedGpuMemory::Used(bytes) => Some(bytes),
+            UsedGpuMemory::Unavailable => None,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+#[deny(unused_mut)]
+mod test {
+    #[cfg(target_os = "linux")]
+    use crate::bitmasks::event::*;
+    #[cfg(target_os = "windows")]
+    use crate::bitmasks::Behavior;
+    use crate::enum_wrappers::device::*;
+    use crate::enums::device::{GpuLockedClocksSetting, SampleValue, UsedGpuMemory};
+    use crate::error::*;
+    use crate::struct_wrappers::device::{
+        ProcessInfo, ProcessUtilizationSample, Sample, SampleBuffer,
+    };
+    use crate::structs::device::{FieldId, PendingRetiredPages};
+    use crate::sys_exports::field_id::*;
+    use crate::test_utils::*;
+    use std::time::{Duration, Instant};
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    #[cfg(target_os = "linux")]
+    fn clear_cpu_affinity() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        device.clear_cpu_affinity().unwrap();
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn is_api_restricted() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.is_api_restricted(Api::ApplicationClocks)?;
+            device.is_api_restricted(Api::AutoBoostedClocks)
+        })
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn applications_clock() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let gfx_clock = device.applications_clock(Clock::Graphics)?;
+            let sm_clock = device.applications_clock(Clock::SM)?;
+            let mem_clock = device.applications_clock(Clock::Memory)?;
+            let vid_clock = device.applications_clock(Clock::Video)?;
+
+            Ok(format!(
+                "Graphics Clock: {}, SM Clock: {}, Memory Clock: {}, Video Clock: {}",
+                gfx_clock, sm_clock, mem_clock, vid_clock
+            ))
+        })
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn auto_boosted_clocks_enabled() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.auto_boosted_clocks_enabled())
+    }
+
+    #[test]
+    fn bar1_memory_info() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.bar1_memory_info())
+    }
+
+    #[test]
+    fn board_id() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.board_id())
+    }
+
+    #[test]
+    fn brand() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.brand())
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn bridge_chip_info() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.bridge_chip_info())
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn bridge_chip_info_display() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let hierarchy = device.bridge_chip_info()?;
+            assert_eq!(
+                hierarchy.chips_hierarchy.len(),
+                hierarchy.chip_count as usize
+            );
+
+            Ok(hierarchy.to_string())
+        })
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn clock() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.clock(Clock::Graphics, ClockId::Current)?;
+            device.clock(Clock::SM, ClockId::TargetAppClock)?;
+            device.clock(Clock::Memory, ClockId::DefaultAppClock)?;
+            device.clock(Clock::Video, ClockId::TargetAppClock)
+            // My machine does not support CustomerMaxBoost
+        })
+    }
+
+    #[test]
+    fn clock_table() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.clock_table(Clock::Graphics)?;
+            device.clock_table(Clock::SM)?;
+            device.clock_table(Clock::Memory)?;
+            device.clock_table(Clock::Video)
+        })
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn max_customer_boost_clock() {
+        let nvml = nvml();
         test_with_device(3, &nvml, |device| {
             device.max_customer_boost_clock(Clock::Graphics)?;
             device.max_customer_boost_clock(Clock::SM)?;
@@ -5222,12 +8171,47 @@ mod test {
         test_with_device(3, &nvml, |device| device.current_pcie_link_width())
     }
 
+    #[test]
+    fn pcie_bandwidth_gbps() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let bandwidth = device.pcie_bandwidth_gbps()?;
+            assert!(bandwidth > 0.0);
+
+            Ok(bandwidth)
+        })
+    }
+
     #[test]
     fn decoder_utilization() {
         let nvml = nvml();
         test_with_device(3, &nvml, |device| device.decoder_utilization())
     }
 
+    #[test]
+    fn jpeg_utilization() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| match device.jpeg_utilization() {
+            Err(NvmlError::NotSupported) => Ok(None),
+            v => v.map(Some),
+        })
+    }
+
+    #[test]
+    fn ofa_utilization() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| match device.ofa_utilization() {
+            Err(NvmlError::NotSupported) => Ok(None),
+            v => v.map(Some),
+        })
+    }
+
+    #[test]
+    fn all_engine_utilization() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.all_engine_utilization())
+    }
+
     #[test]
     #[ignore = "my machine does not support this call"]
     fn default_applications_clock() {
@@ -5257,87 +8241,386 @@ mod test {
         test_with_device(3, &nvml, |device| device.is_display_connected())
     }
 
-    #[cfg(target_os = "windows")]
     #[test]
-    fn driver_model() {
-        let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.driver_model())
+    fn display_status() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.display_status())
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn driver_model() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.driver_model())
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn driver_model_or_none() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.driver_model_or_none())
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    #[test]
+    fn driver_model_or_none_elsewhere() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            assert!(device.driver_model_or_none()?.is_none());
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn is_ecc_enabled() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.is_ecc_enabled())
+    }
+
+    #[test]
+    fn encoder_utilization() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.encoder_utilization())
+    }
+
+    #[test]
+    fn encoder_capacity() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.encoder_capacity(EncoderType::H264)
+        })
+    }
+
+    #[test]
+    fn encoder_capacities() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.encoder_capacities())
+    }
+
+    #[test]
+    fn encoder_stats() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.encoder_stats())
+    }
+
+    #[test]
+    fn encoder_sessions() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.encoder_sessions())
+    }
+
+    #[test]
+    fn fbc_stats() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.fbc_stats())
+    }
+
+    #[test]
+    fn fbc_sessions_info() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.fbc_sessions_info())
+    }
+
+    #[test]
+    fn enforced_power_limit() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.enforced_power_limit())
+    }
+
+    #[test]
+    fn enforced_power_limit_watts() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.enforced_power_limit_watts())
+    }
+
+    #[test]
+    fn fan_speed() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.fan_speed(0))
+    }
+
+    #[test]
+    fn num_fans() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.num_fans())
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn gpu_operation_mode() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.gpu_operation_mode())
+    }
+
+    #[test]
+    fn gpu_operation_mode_pending_differs() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            match device.gpu_operation_mode_pending_differs() {
+                Err(NvmlError::NotSupported) => Ok(false),
+                v => v,
+            }
+        })
+    }
+
+    #[test]
+    fn total_process_used_memory() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.total_process_used_memory())
+    }
+
+    #[test]
+    fn sum_used_gpu_memory_skips_unavailable_and_duplicates() {
+        let process = |pid, used_gpu_memory| ProcessInfo {
+            pid,
+            used_gpu_memory,
+            gpu_instance_id: None,
+            compute_instance_id: None,
+        };
+
+        let processes = [
+            process(1, UsedGpuMemory::Used(100)),
+            process(2, UsedGpuMemory::Unavailable),
+            process(3, UsedGpuMemory::Used(50)),
+            // The same process may be listed as both compute and graphics
+            process(1, UsedGpuMemory::Used(100)),
+        ];
+
+        assert_eq!(super::sum_used_gpu_memory(&processes), 150);
+        assert_eq!(super::sum_used_gpu_memory(&[]), 0);
+    }
+
+    #[test]
+    fn is_clock_throttled() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let gfx = device.is_clock_throttled(Clock::Graphics)?;
+            let sm = device.is_clock_throttled(Clock::SM)?;
+            let mem = device.is_clock_throttled(Clock::Memory)?;
+
+            Ok(format!(
+                "Graphics throttled: {}, SM throttled: {}, Memory throttled: {}",
+                gfx, sm, mem
+            ))
+        })
+    }
+
+    #[test]
+    fn is_boosting() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.is_boosting())
+    }
+
+    #[test]
+    fn retry_with_required_size_grows_buffer() {
+        let mut sizes = vec![];
+        let res = super::retry_with_required_size(2, |size| {
+            sizes.push(size);
+
+            match size {
+                2 => Err(NvmlError::InsufficientSize(Some(3))),
+                3 => Err(NvmlError::InsufficientSize(Some(5))),
+                _ => Ok(size),
+            }
+        });
+
+        assert_eq!(res.unwrap(), 5);
+        assert_eq!(sizes, vec![2, 3, 5]);
+    }
+
+    #[test]
+    fn retry_with_required_size_is_bounded() {
+        let mut calls = 0;
+        let res: Result<(), _> = super::retry_with_required_size(1, |size| {
+            calls += 1;
+            Err(NvmlError::InsufficientSize(Some(size + 1)))
+        });
+
+        assert!(matches!(res, Err(NvmlError::InsufficientSize(Some(_)))));
+        assert_eq!(calls, super::MAX_INSUFFICIENT_SIZE_RETRIES + 1);
+    }
+
+    #[test]
+    fn retry_with_required_size_passes_through_errors() {
+        let mut calls = 0;
+        let res: Result<(), _> = super::retry_with_required_size(1, |_| {
+            calls += 1;
+            Err(NvmlError::NotSupported)
+        });
+
+        assert!(matches!(res, Err(NvmlError::NotSupported)));
+        assert_eq!(calls, 1);
     }
 
     #[test]
-    #[ignore = "my machine does not support this call"]
-    fn is_ecc_enabled() {
+    fn eq_and_hash() {
+        use std::collections::HashMap;
+
         let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.is_ecc_enabled())
+        let first = device(&nvml);
+        let second = device(&nvml);
+
+        assert_eq!(first, second);
+
+        let mut names = HashMap::new();
+        names.insert(first, "first");
+        assert_eq!(names.get(&second), Some(&"first"));
+
+        if nvml.device_count().expect("device count") > 1 {
+            let other = nvml.device_by_index(1).expect("device");
+            assert_ne!(second, other);
+        }
     }
 
     #[test]
-    fn encoder_utilization() {
+    fn duplicate() {
         let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.encoder_utilization())
+        let duplicate = {
+            let original = device(&nvml);
+            let duplicate = original.duplicate();
+
+            assert_eq!(original, duplicate);
+            duplicate
+        };
+
+        duplicate.uuid().expect("uuid");
     }
 
     #[test]
-    fn encoder_capacity() {
+    fn power_headroom() {
         let nvml = nvml();
         test_with_device(3, &nvml, |device| {
-            device.encoder_capacity(EncoderType::H264)
+            let headroom = device.power_headroom()?;
+            assert!(headroom >= 0.0);
+
+            Ok(format!("{:.3}", headroom))
         })
     }
 
     #[test]
-    fn encoder_stats() {
+    fn power_limit_fraction_of_default() {
         let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.encoder_stats())
+        test_with_device(3, &nvml, |device| {
+            let fraction = device.power_limit_fraction_of_default()?;
+            assert!(fraction > 0.0);
+
+            Ok(format!("{:.3}", fraction))
+        })
     }
 
     #[test]
-    fn encoder_sessions() {
-        let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.encoder_sessions())
+    fn milliwatts_to_watts() {
+        use super::milliwatts_to_watts;
+
+        assert_eq!(milliwatts_to_watts(250_000), 250.0);
+        assert_eq!(milliwatts_to_watts(72_500), 72.5);
+        assert_eq!(milliwatts_to_watts(1), 0.001);
+        assert_eq!(milliwatts_to_watts(0), 0.0);
+        assert_eq!(milliwatts_to_watts(u32::MAX), 4_294_967.295);
     }
 
     #[test]
-    fn fbc_stats() {
-        let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.fbc_stats())
+    fn fraction() {
+        use super::fraction;
+
+        assert_eq!(fraction(150_000, 300_000), 0.5);
+        assert_eq!(fraction(300_000, 300_000), 1.0);
+        assert_eq!(fraction(330_000, 300_000), 1.1);
+        assert_eq!(fraction(0, 300_000), 0.0);
+        assert_eq!(fraction(150_000, 0), 0.0);
+        assert_eq!(fraction(u32::MAX, u32::MAX), 1.0);
     }
 
     #[test]
-    fn fbc_sessions_info() {
+    fn process_utilization_for_pid() {
         let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.fbc_sessions_info())
+        test_with_device(3, &nvml, |device| {
+            device.process_utilization_for_pid(std::process::id(), None)?;
+            Ok(())
+        })
     }
 
     #[test]
-    fn enforced_power_limit() {
-        let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.enforced_power_limit())
+    fn latest_sample_for_pid() {
+        let sample = |pid, timestamp, sm_util| ProcessUtilizationSample {
+            pid,
+            timestamp,
+            sm_util,
+            mem_util: 0,
+            enc_util: 0,
+            dec_util: 0,
+        };
+
+        let samples = vec![
+            sample(10, 100, 5),
+            sample(20, 150, 30),
+            sample(10, 200, 50),
+            sample(10, 120, 7),
+        ];
+
+        assert_eq!(
+            super::latest_sample_for_pid(samples.clone(), 10),
+            Some(sample(10, 200, 50))
+        );
+        assert_eq!(
+            super::latest_sample_for_pid(samples.clone(), 20),
+            Some(sample(20, 150, 30))
+        );
+        assert_eq!(super::latest_sample_for_pid(samples, 30), None);
     }
 
     #[test]
-    fn fan_speed() {
-        let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.fan_speed(0))
+    fn clock_below_max() {
+        use super::clock_below_max;
+
+        // At or near max
+        assert!(!clock_below_max(1800, 1800));
+        assert!(!clock_below_max(1795, 1800));
+        assert!(!clock_below_max(1710, 1800));
+        // Clocks above max can be reported while boosting
+        assert!(!clock_below_max(1900, 1800));
+        assert!(!clock_below_max(0, 0));
+
+        // Significantly below max
+        assert!(clock_below_max(1709, 1800));
+        assert!(clock_below_max(300, 1800));
+        assert!(clock_below_max(0, 1800));
+
+        // No overflow near the top of the range
+        assert!(!clock_below_max(u32::MAX, u32::MAX));
     }
 
     #[test]
-    fn num_fans() {
-        let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.num_fans())
+    fn clock_above_default() {
+        use super::clock_above_default;
+
+        assert!(clock_above_default(1900, 1500));
+        assert!(clock_above_default(1501, 1500));
+
+        assert!(!clock_above_default(1500, 1500));
+        assert!(!clock_above_default(300, 1500));
+        assert!(!clock_above_default(0, 0));
     }
 
     #[test]
-    #[ignore = "my machine does not support this call"]
-    fn gpu_operation_mode() {
+    fn running_graphics_processes() {
         let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.gpu_operation_mode())
+        test_with_device(3, &nvml, |device| device.running_graphics_processes())
     }
 
     #[test]
-    fn running_graphics_processes() {
+    #[ignore = "requires a device with no running processes"]
+    fn running_processes_idle() {
         let nvml = nvml();
-        test_with_device(3, &nvml, |device| device.running_graphics_processes())
+        test_with_device(3, &nvml, |device| {
+            assert_eq!(device.running_compute_processes()?, vec![]);
+            assert_eq!(device.running_graphics_processes()?, vec![]);
+            assert_eq!(device.running_compute_processes_count()?, 0);
+            assert_eq!(device.running_graphics_processes_count()?, 0);
+
+            Ok(())
+        })
     }
 
     #[cfg(feature = "legacy-functions")]
@@ -5384,6 +8667,32 @@ mod test {
         })
     }
 
+    #[test]
+    fn clocks_typed() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let clock = device.clock_info_typed(Clock::Graphics)?;
+            let max_clock = device.max_clock_info_typed(Clock::Graphics)?;
+
+            assert_eq!(clock.mhz(), device.clock_info(Clock::Graphics)?);
+            assert_eq!(max_clock.mhz(), device.max_clock_info(Clock::Graphics)?);
+
+            Ok(format!("Graphics Clock: {}, Max: {}", clock, max_clock))
+        })
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn applications_clocks_typed() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.applications_clock_typed(Clock::Graphics)?;
+            device.default_applications_clock_typed(Clock::Graphics)?;
+            device.max_customer_boost_clock_typed(Clock::Graphics)?;
+            device.clock_typed(Clock::Graphics, ClockId::Current)
+        })
+    }
+
     #[test]
     fn max_clock_info() {
         let nvml = nvml();
@@ -5406,6 +8715,18 @@ mod test {
         test_with_device(3, &nvml, |device| device.max_pcie_link_gen())
     }
 
+    #[test]
+    fn gpu_max_pcie_link_gen() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let device_max = device.gpu_max_pcie_link_gen()?;
+            let system_max = device.max_pcie_link_gen()?;
+
+            assert!(system_max <= device_max);
+            Ok(device_max)
+        })
+    }
+
     #[test]
     fn max_pcie_link_width() {
         let nvml = nvml();
@@ -5438,6 +8759,65 @@ mod test {
         test_with_device(3, &nvml, |device| device.minor_number())
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn stable_local_id() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let id = device.stable_local_id()?;
+            assert_eq!(id, device.minor_number()?.to_string());
+
+            Ok(id)
+        })
+    }
+
+    #[cfg(target_os = "windows")]
+    #[test]
+    fn stable_local_id() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let id = device.stable_local_id()?;
+            assert_eq!(id, device.pci_info()?.bus_id);
+
+            Ok(id)
+        })
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn numa_node() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.numa_node())
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn sysfs_pci_address() {
+        use super::sysfs_pci_address;
+
+        assert_eq!(
+            sysfs_pci_address("00000000:3B:00.0").as_deref(),
+            Some("0000:3b:00.0")
+        );
+        assert_eq!(
+            sysfs_pci_address("0001:01:1F.7").as_deref(),
+            Some("0001:01:1f.7")
+        );
+        assert_eq!(sysfs_pci_address("not a bus id"), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn parse_numa_node() {
+        use super::parse_numa_node;
+
+        assert_eq!(parse_numa_node("0\n").unwrap(), Some(0));
+        assert_eq!(parse_numa_node("3").unwrap(), Some(3));
+        assert_eq!(parse_numa_node("-1\n").unwrap(), None);
+        assert!(matches!(parse_numa_node(""), Err(NvmlError::Unknown)));
+        assert!(matches!(parse_numa_node("node0"), Err(NvmlError::Unknown)));
+    }
+
     #[test]
     fn is_multi_gpu_board() {
         let nvml = nvml();
@@ -5456,12 +8836,33 @@ mod test {
         test_with_device(3, &nvml, |device| device.pci_info())
     }
 
+    #[test]
+    fn is_function_zero() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.is_function_zero())
+    }
+
+    #[test]
+    fn verify_pci_bus_id() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            assert!(device.verify_pci_bus_id()?);
+            Ok(())
+        })
+    }
+
     #[test]
     fn pcie_replay_counter() {
         let nvml = nvml();
         test_with_device(3, &nvml, |device| device.pcie_replay_counter())
     }
 
+    #[test]
+    fn pcie_replay_and_errors() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.pcie_replay_and_errors())
+    }
+
     #[test]
     fn pcie_throughput() {
         let nvml = nvml();
@@ -5471,6 +8872,59 @@ mod test {
         })
     }
 
+    #[test]
+    fn pcie_throughput_averaged() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            // A single sample is just one read, so it must not wait on the interval
+            let start = Instant::now();
+            let throughput = device.pcie_throughput_averaged(
+                PcieUtilCounter::Send,
+                1,
+                Duration::from_secs(10),
+            )?;
+            assert!(start.elapsed() < Duration::from_secs(10));
+
+            Ok(throughput)
+        })
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidArg")]
+    fn pcie_throughput_averaged_zero_samples() {
+        let nvml = nvml();
+        let device = device(&nvml);
+
+        device
+            .pcie_throughput_averaged(PcieUtilCounter::Send, 0, Duration::ZERO)
+            .unwrap();
+    }
+
+    #[test]
+    fn average_power_usage() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            // A window shorter than the interval is a single read, so it must
+            // not wait on the interval
+            let start = Instant::now();
+            let usage = device.average_power_usage(Duration::ZERO, Duration::from_secs(10))?;
+            assert!(start.elapsed() < Duration::from_secs(10));
+
+            Ok(usage)
+        })
+    }
+
+    #[test]
+    #[should_panic(expected = "InvalidArg")]
+    fn average_power_usage_zero_interval() {
+        let nvml = nvml();
+        let device = device(&nvml);
+
+        device
+            .average_power_usage(Duration::from_secs(1), Duration::ZERO)
+            .unwrap();
+    }
+
     #[test]
     fn performance_state() {
         let nvml = nvml();
@@ -5496,6 +8950,12 @@ mod test {
         test_with_device(3, &nvml, |device| device.power_management_limit())
     }
 
+    #[test]
+    fn power_management_limit_watts() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.power_management_limit_watts())
+    }
+
     #[test]
     fn power_management_limit_constraints() {
         let nvml = nvml();
@@ -5512,6 +8972,12 @@ mod test {
         test_with_device(3, &nvml, |device| device.is_power_management_algo_active())
     }
 
+    #[test]
+    fn is_power_management_supported() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.is_power_management_supported())
+    }
+
     #[test]
     fn power_state() {
         let nvml = nvml();
@@ -5526,6 +8992,18 @@ mod test {
         test_with_device(3, &nvml, |device| device.power_usage())
     }
 
+    #[test]
+    fn power_usage_watts() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.power_usage_watts())
+    }
+
+    #[test]
+    fn power_scaling_info() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.power_scaling_info())
+    }
+
     #[test]
     #[ignore = "my machine does not support this call"]
     fn retired_pages() {
@@ -5543,6 +9021,18 @@ mod test {
         test_with_device(3, &nvml, |device| device.are_pages_pending_retired())
     }
 
+    #[test]
+    fn pending_retired_pages() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| match device.pending_retired_pages() {
+            Err(NvmlError::NotSupported) => Ok(PendingRetiredPages {
+                single_bit: false,
+                double_bit: false,
+            }),
+            v => v,
+        })
+    }
+
     #[test]
     #[ignore = "my machine does not support this call"]
     fn samples() {
@@ -5553,6 +9043,99 @@ mod test {
         })
     }
 
+    #[test]
+    fn samples_into() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let mut buf = vec![Sample {
+                timestamp: 0,
+                value: SampleValue::U32(0),
+            }];
+            // NVML keeps far fewer samples than this
+            buf.reserve(1024);
+            let ptr = buf.as_ptr();
+            let capacity = buf.capacity();
+
+            for _ in 0..2 {
+                match device.samples_into(Sampling::GpuUtilization, None, &mut buf) {
+                    // `NotFound` means no samples have been taken yet
+                    Ok(()) | Err(NvmlError::NotFound) => (),
+                    Err(e) => return Err(e),
+                }
+
+                assert!(buf.iter().all(|s| s.timestamp != 0));
+                assert_eq!(buf.as_ptr(), ptr);
+                assert_eq!(buf.capacity(), capacity);
+            }
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn samples_into_buffer() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            // NVML keeps far fewer samples than this
+            let mut buf = SampleBuffer::with_capacity(1024);
+            let raw_ptr = buf.raw.as_ptr();
+            let samples_ptr = buf.samples.as_ptr();
+            let capacity = buf.capacity();
+
+            for _ in 0..2 {
+                match device.samples_into_buffer(Sampling::GpuUtilization, None, &mut buf) {
+                    // `NotFound` means no samples have been taken yet
+                    Ok(()) | Err(NvmlError::NotFound) => (),
+                    Err(e) => return Err(e),
+                }
+
+                assert_eq!(buf.raw.len(), buf.samples().len());
+                assert_eq!(buf.raw.as_ptr(), raw_ptr);
+                assert_eq!(buf.samples.as_ptr(), samples_ptr);
+                assert_eq!(buf.capacity(), capacity);
+            }
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn samples_typed() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let samples = match device.samples_typed::<u32, _>(Sampling::GpuUtilization, None) {
+                // `NotFound` means no samples have been taken yet
+                Err(NvmlError::NotFound) => return Ok(()),
+                other => other?,
+            };
+
+            if !samples.is_empty() {
+                assert!(matches!(
+                    device.samples_typed::<f64, _>(Sampling::GpuUtilization, None),
+                    Err(NvmlError::SampleValueTypeMismatch(_))
+                ));
+            }
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn samples_media_engines() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            for sampling in [Sampling::EncoderUtilization, Sampling::DecoderUtilization] {
+                match device.samples(sampling, None) {
+                    // `NotFound` means no samples have been taken yet
+                    Ok(_) | Err(NvmlError::NotSupported) | Err(NvmlError::NotFound) => (),
+                    Err(e) => return Err(e),
+                }
+            }
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn field_values_for() {
         let nvml = nvml();
@@ -5600,6 +9183,47 @@ mod test {
         })
     }
 
+    #[test]
+    fn field_values_for_scoped() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let samples = device.field_values_for_scoped(&[
+                (FieldId(NVML_FI_DEV_MEMORY_TEMP), 0),
+                (FieldId(NVML_FI_DEV_NVLINK_SPEED_MBPS_COMMON), 1),
+            ])?;
+
+            assert_eq!(samples.len(), 2);
+
+            for (sample, scope_id) in samples.iter().zip([0, 1]) {
+                assert_eq!(sample.as_ref().map(|s| s.scope_id).ok(), Some(scope_id));
+            }
+
+            Ok(samples)
+        })
+    }
+
+    #[test]
+    fn detailed_ecc_errors_modern() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.detailed_ecc_errors_modern(MemoryError::Corrected, EccCounter::Volatile)?;
+            device.detailed_ecc_errors_modern(MemoryError::Uncorrected, EccCounter::Aggregate)
+        })
+    }
+
+    #[test]
+    fn memory_temperature() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.memory_temperature())
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn nvlink_speed_mbps_common() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.nvlink_speed_mbps_common())
+    }
+
     // Passing an empty slice should return an `InvalidArg` error
     #[should_panic(expected = "InvalidArg")]
     #[test]
@@ -5608,6 +9232,53 @@ mod test {
         test_with_device(3, &nvml, |device| device.field_values_for(&[]))
     }
 
+    #[test]
+    fn field_values_chunked() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let ids = [
+                FieldId(NVML_FI_DEV_ECC_CURRENT),
+                FieldId(NVML_FI_DEV_ECC_PENDING),
+                FieldId(NVML_FI_DEV_MEMORY_TEMP),
+                FieldId(NVML_FI_DEV_PCIE_REPLAY_COUNTER),
+                FieldId(NVML_FI_DEV_TOTAL_ENERGY_CONSUMPTION),
+            ];
+
+            // A chunk size of 2 puts chunk boundaries between the 2nd/3rd and
+            // 4th/5th IDs
+            let samples = device.field_values_chunked(&ids, 2)?;
+            assert_eq!(samples.len(), ids.len());
+
+            for (sample, id) in samples.iter().zip(ids.iter()) {
+                if let Ok(sample) = sample {
+                    assert_eq!(&sample.field, id);
+                }
+            }
+
+            Ok(samples)
+        })
+    }
+
+    #[should_panic(expected = "InvalidArg")]
+    #[test]
+    fn field_values_chunked_zero_chunk_size() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device.field_values_chunked(&[FieldId(NVML_FI_DEV_ECC_CURRENT)], 0)
+        })
+    }
+
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn clear_field_values() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        device
+            .clear_field_values(&[FieldId(NVML_FI_DEV_PCIE_REPLAY_COUNTER)])
+            .unwrap();
+    }
+
     #[test]
     #[ignore = "my machine does not support this call"]
     fn serial() {
@@ -5634,6 +9305,18 @@ mod test {
         test_with_device(3, &nvml, |device| device.current_throttle_reasons_strict())
     }
 
+    #[test]
+    fn current_clocks_event_reasons() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.current_clocks_event_reasons())
+    }
+
+    #[test]
+    fn supported_clocks_event_reasons() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.supported_clocks_event_reasons())
+    }
+
     #[test]
     fn supported_throttle_reasons() {
         let nvml = nvml();
@@ -5652,9 +9335,10 @@ mod test {
     #[ignore = "my machine does not support this call"]
     fn supported_graphics_clocks() {
         let nvml = nvml();
-        #[allow(unused_variables)]
         test_with_device(3, &nvml, |device| {
             let supported = device.supported_graphics_clocks(810)?;
+            assert!(supported.windows(2).all(|w| w[0] > w[1]));
+
             Ok(())
         })
     }
@@ -5663,9 +9347,21 @@ mod test {
     #[ignore = "my machine does not support this call"]
     fn supported_memory_clocks() {
         let nvml = nvml();
-        #[allow(unused_variables)]
         test_with_device(3, &nvml, |device| {
             let supported = device.supported_memory_clocks()?;
+            assert!(supported.windows(2).all(|w| w[0] > w[1]));
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn supported_memory_clock_range() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let (min, max) = device.supported_memory_clock_range()?;
+            assert!(min <= max);
 
             Ok(())
         })
@@ -5679,6 +9375,30 @@ mod test {
         })
     }
 
+    #[test]
+    fn thermal_settings() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| match device.thermal_settings() {
+            Err(NvmlError::NotSupported) => Ok(vec![]),
+            v => v,
+        })
+    }
+
+    #[test]
+    fn thermal_sensor_count() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| match device.thermal_sensor_count() {
+            Err(NvmlError::NotSupported) => Ok(0),
+            v => v,
+        })
+    }
+
+    #[test]
+    fn temperatures() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.temperatures())
+    }
+
     #[test]
     fn temperature_threshold() {
         let nvml = nvml();
@@ -5690,6 +9410,19 @@ mod test {
         })
     }
 
+    #[test]
+    fn temperature_thresholds() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let thresholds = device.temperature_thresholds()?;
+
+            assert!(thresholds.shutdown.is_some());
+            assert!(thresholds.slowdown.is_some());
+
+            Ok(thresholds)
+        })
+    }
+
     // I do not have 2 devices
     #[ignore = "my machine does not support this call"]
     #[cfg(target_os = "linux")]
@@ -5739,6 +9472,51 @@ mod test {
         test_with_device(3, &nvml, |device| device.vbios_version())
     }
 
+    #[test]
+    fn performance_report() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let report = device.performance_report()?;
+
+            assert!(report.graphics_clock.is_some());
+            assert!(report.memory_clock.is_some());
+            assert!(report.performance_state.is_some());
+            assert!(report.temperature.is_some());
+
+            Ok(report)
+        })
+    }
+
+    #[test]
+    fn full_utilization() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let utilization = device.full_utilization()?;
+
+            assert!(utilization.gpu <= 100);
+            assert!(utilization.memory <= 100);
+
+            Ok(utilization)
+        })
+    }
+
+    #[test]
+    fn version_info() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let info = device.version_info()?;
+
+            assert!(!info.driver.is_empty());
+            assert_eq!(info.driver, nvml.sys_driver_version()?);
+
+            if let Some(vbios) = &info.vbios {
+                assert!(!vbios.is_empty());
+            }
+
+            Ok(info)
+        })
+    }
+
     #[test]
     fn violation_status() {
         let nvml = nvml();
@@ -5747,6 +9525,30 @@ mod test {
         })
     }
 
+    #[test]
+    fn violation_status_newer_policies() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let policies = [
+                PerformancePolicy::SyncBoost,
+                PerformancePolicy::BoardLimit,
+                PerformancePolicy::LowUtilization,
+                PerformancePolicy::Reliability,
+                PerformancePolicy::TotalAppClocks,
+                PerformancePolicy::TotalBaseClocks,
+            ];
+
+            for policy in policies {
+                match device.violation_status(policy) {
+                    Ok(_) | Err(NvmlError::NotSupported) => (),
+                    Err(e) => return Err(e),
+                }
+            }
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn num_cores() {
         let nvml = nvml();
@@ -5846,6 +9648,16 @@ mod test {
         test_with_device(3, &nvml, |device| device.validate_info_rom())
     }
 
+    #[test]
+    fn inforom_health() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| match device.inforom_health() {
+            Ok(health) => Ok(Some(health)),
+            Err(NvmlError::NotSupported) => Ok(None),
+            Err(e) => Err(e),
+        })
+    }
+
     // This modifies device state, so we don't want to actually run the test
     #[allow(dead_code)]
     fn clear_accounting_pids() {
@@ -5897,6 +9709,24 @@ mod test {
         device.set_accounting(true).expect("set to true")
     }
 
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn ensure_accounting_enabled() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        match device.ensure_accounting_enabled() {
+            Ok(_) | Err(NvmlError::NotSupported) => (),
+            Err(e) => panic!("{:?}", e),
+        }
+
+        match device.ensure_accounting_enabled() {
+            Ok(changed) => assert!(!changed),
+            Err(NvmlError::NotSupported) => (),
+            Err(e) => panic!("{:?}", e),
+        }
+    }
+
     // This modifies device state, so we don't want to actually run the test
     #[allow(dead_code)]
     fn clear_ecc_error_counts() {
@@ -5908,6 +9738,17 @@ mod test {
             .expect("set to true")
     }
 
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn clear_all_ecc_error_counts() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        device
+            .clear_all_ecc_error_counts()
+            .expect("cleared both counters")
+    }
+
     // This modifies device state, so we don't want to actually run the test
     #[allow(dead_code)]
     fn set_api_restricted() {
@@ -5919,6 +9760,15 @@ mod test {
             .expect("set to true")
     }
 
+    // This modifies device state, so we don't want to actually run the test
+    #[allow(dead_code)]
+    fn set_all_api_restrictions() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        device.set_all_api_restrictions(true).expect("set to true")
+    }
+
     // This modifies device state, so we don't want to actually run the test
     #[allow(dead_code)]
     fn set_applications_clocks() {
@@ -5928,6 +9778,36 @@ mod test {
         device.set_applications_clocks(32, 32).expect("set to true")
     }
 
+    #[test]
+    fn set_applications_clocks_checked_rejects_unsupported() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        let max_mem_clock = *device
+            .supported_memory_clocks()
+            .expect("memory clocks")
+            .iter()
+            .max()
+            .expect("at least one memory clock");
+        let max_graphics_clock = *device
+            .supported_graphics_clocks(max_mem_clock)
+            .expect("graphics clocks")
+            .iter()
+            .max()
+            .expect("at least one graphics clock");
+
+        match device.set_applications_clocks_checked(max_mem_clock + 1, max_graphics_clock + 1) {
+            Err(NvmlError::UnsupportedClocks {
+                nearest_mem_clock,
+                nearest_graphics_clock,
+            }) => {
+                assert_eq!(nearest_mem_clock, max_mem_clock);
+                assert_eq!(nearest_graphics_clock, max_graphics_clock);
+            }
+            other => panic!("expected `UnsupportedClocks`, got {:?}", other),
+        }
+    }
+
     // This modifies device state, so we don't want to actually run the test
     #[allow(dead_code)]
     fn set_compute_mode() {
@@ -5951,6 +9831,19 @@ mod test {
             .expect("set to wdm")
     }
 
+    // This depends on device state set by `set_driver_model()`, so we don't
+    // want to actually run the test
+    #[cfg(target_os = "windows")]
+    #[allow(dead_code)]
+    fn driver_model_change_pending() {
+        let nvml = nvml();
+        let device = device(&nvml);
+
+        device
+            .driver_model_change_pending()
+            .expect("driver model change pending");
+    }
+
     // This modifies device state, so we don't want to actually run the test
     #[allow(dead_code)]
     fn set_gpu_locked_clocks() {
@@ -5985,6 +9878,42 @@ mod test {
             .expect("set to a range")
     }
 
+    // This modifies device state if validation is broken, so we don't want to
+    // actually run the test
+    #[allow(dead_code)]
+    fn set_mem_locked_clocks_checked_rejects_out_of_range() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        let (min, max) = device
+            .supported_memory_clock_range()
+            .expect("memory clock range");
+
+        match device.set_mem_locked_clocks_checked(min, max + 1) {
+            Err(NvmlError::ClocksOutOfRange {
+                min_supported,
+                max_supported,
+            }) => {
+                assert_eq!(min_supported, min);
+                assert_eq!(max_supported, max);
+            }
+            other => panic!("expected `ClocksOutOfRange`, got {:?}", other),
+        }
+    }
+
+    // This modifies device state if validation is broken, so we don't want to
+    // actually run the test
+    #[allow(dead_code)]
+    fn set_mem_locked_clocks_checked_rejects_inverted_range() {
+        let nvml = nvml();
+        let mut device = device(&nvml);
+
+        match device.set_mem_locked_clocks_checked(1139, 1048) {
+            Err(NvmlError::InvalidArg) => (),
+            other => panic!("expected `InvalidArg`, got {:?}", other),
+        }
+    }
+
     // This modifies device state, so we don't want to actually run the test
     #[allow(dead_code)]
     fn reset_mem_locked_clocks() {
@@ -6055,6 +9984,31 @@ mod test {
         })
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn register_events_returns_set_on_error() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let set = nvml.create_event_set()?;
+
+            // Few devices support every event type. If registration fails with
+            // anything other than `Unknown`, the set should be handed back and
+            // still be usable.
+            match device.register_events(EventTypes::all(), set) {
+                Ok(set) => set.release_events(),
+                Err(e) if e.set.is_none() => Err(e.error),
+                Err(e) => {
+                    let set = e.set.expect("set");
+                    let set = device
+                        .register_events(device.supported_event_types()?, set)
+                        .map_err(|e| e.error)?;
+
+                    set.release_events()
+                }
+            }
+        })
+    }
+
     #[cfg(target_os = "linux")]
     #[test]
     fn supported_event_types() {
@@ -6075,4 +10029,131 @@ mod test {
         let nvml = nvml();
         test_with_device(3, &nvml, |device| device.is_drain_enabled(None))
     }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn supported_vgpus() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let types = device.supported_vgpus()?;
+            Ok(types.iter().map(|t| t.id()).collect::<Vec<_>>())
+        })
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn creatable_vgpus() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let types = device.creatable_vgpus()?;
+            Ok(types.iter().map(|t| t.id()).collect::<Vec<_>>())
+        })
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn active_vgpus() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let instances = device.active_vgpus()?;
+            Ok(instances.iter().map(|i| i.instance()).collect::<Vec<_>>())
+        })
+    }
+
+    #[test]
+    fn vgpu_metadata() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| match device.vgpu_metadata() {
+            Ok(metadata) => Ok(Some(metadata.version())),
+            Err(NvmlError::NotSupported) => Ok(None),
+            Err(e) => Err(e),
+        })
+    }
+
+    #[test]
+    fn is_migration_capable() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| match device.is_migration_capable() {
+            Err(NvmlError::NotSupported) => Ok(false),
+            other => other,
+        })
+    }
+
+    #[test]
+    fn vgpu_capability() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            match device.vgpu_capability(DeviceVgpuCapability::FractionalMultiVgpu) {
+                Err(NvmlError::NotSupported) => Ok(false),
+                other => other,
+            }
+        })
+    }
+
+    #[test]
+    fn capability_report() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let report = device.capability_report();
+
+            for method in &["name", "uuid", "pci_info", "memory_info", "brand"] {
+                assert!(report.get(method).is_some(), "missing {}", method);
+            }
+
+            Ok(report)
+        })
+    }
+
+    #[test]
+    fn error_kind() {
+        use super::error_kind;
+
+        assert_eq!(error_kind(&NvmlError::NotSupported), "NotSupported");
+        assert_eq!(
+            error_kind(&NvmlError::UnexpectedVariant(3)),
+            "UnexpectedVariant"
+        );
+        assert_eq!(
+            error_kind(&NvmlError::DeviceIndexOutOfRange { index: 4, count: 2 }),
+            "DeviceIndexOutOfRange"
+        );
+        assert_eq!(
+            error_kind(&NvmlError::FailedToLoadSymbol("nvmlInit_v2".into())),
+            "FailedToLoadSymbol"
+        );
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn nvlink_remote_endpoints() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.nvlink_remote_endpoints())
+    }
+
+    #[test]
+    fn common_version() {
+        assert_eq!(super::common_version(&[]), None);
+        assert_eq!(super::common_version(&[4]), Some(4));
+        assert_eq!(super::common_version(&[4, 4, 4]), Some(4));
+        assert_eq!(super::common_version(&[4, 3, 4]), None);
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn nvlink_common_version() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| device.nvlink_common_version())
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn nvlink_summary() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let summary = device.nvlink_summary()?;
+            assert!(summary.active_count <= summary.link_count);
+
+            Ok(summary)
+        })
+    }
 }
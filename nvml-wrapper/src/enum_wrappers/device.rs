@@ -1,13 +1,16 @@
 use crate::error::NvmlError;
 use crate::ffi::bindings::*;
-#[cfg(feature = "serde")]
+#[cfg(all(feature = "serde", not(feature = "serde-human")))]
 use serde_derive::{Deserialize, Serialize};
 use wrapcenum_derive::EnumWrapper;
 
 /// API types that allow changes to default permission restrictions.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlRestrictedAPI_enum")]
 pub enum Api {
     /**
@@ -25,10 +28,19 @@ pub enum Api {
     AutoBoostedClocks,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(Api {
+    ApplicationClocks,
+    AutoBoostedClocks
+});
+
 /// Clock types. All speeds are in MHz.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlClockType_enum")]
 pub enum Clock {
     /// Graphics clock domain.
@@ -47,10 +59,21 @@ pub enum Clock {
     Video,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(Clock {
+    Graphics,
+    SM,
+    Memory,
+    Video
+});
+
 /// These are used in combo with `Clock` to specify a single clock value.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlClockId_enum")]
 pub enum ClockId {
     /// Current actual clock value.
@@ -67,10 +90,21 @@ pub enum ClockId {
     CustomerMaxBoost,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(ClockId {
+    Current,
+    TargetAppClock,
+    DefaultAppClock,
+    CustomerMaxBoost
+});
+
 /// GPU brand.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlBrandType_enum")]
 pub enum Brand {
     #[wrap(c_variant = "NVML_BRAND_UNKNOWN")]
@@ -131,6 +165,28 @@ pub enum Brand {
     TitanRTX,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(Brand {
+    Unknown,
+    Quadro,
+    Tesla,
+    NVS,
+    GRID,
+    GeForce,
+    Titan,
+    VApps,
+    VPC,
+    VCS,
+    VWS,
+    CloudGaming,
+    VGaming,
+    QuadroRTX,
+    NvidiaRTX,
+    Nvidia,
+    GeForceRTX,
+    TitanRTX
+});
+
 /**
 Represents type of a bridge chip.
 
@@ -139,7 +195,10 @@ type is, so you're on your own there.
 */
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlBridgeChipType_enum")]
 pub enum BridgeChip {
     #[wrap(c_variant = "NVML_BRIDGE_CHIP_PLX")]
@@ -148,10 +207,16 @@ pub enum BridgeChip {
     BRO4,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(BridgeChip { PLX, BRO4 });
+
 /// Memory error types.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlMemoryErrorType_enum")]
 pub enum MemoryError {
     /**
@@ -172,6 +237,12 @@ pub enum MemoryError {
     Uncorrected,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(MemoryError {
+    Corrected,
+    Uncorrected
+});
+
 /**
 ECC counter types.
 
@@ -183,7 +254,10 @@ If not, volatile counts are reset each time a compute app is run.
 */
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlEccCounterType_enum")]
 pub enum EccCounter {
     /// Volatile counts are reset each time the driver loads.
@@ -195,10 +269,19 @@ pub enum EccCounter {
     Aggregate,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(EccCounter {
+    Volatile,
+    Aggregate
+});
+
 /// Memory locations. See `Device.memory_error_counter()`.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlMemoryLocation_enum")]
 pub enum MemoryLocation {
     /// GPU L1 cache.
@@ -226,12 +309,28 @@ pub enum MemoryLocation {
     SRAM,
 }
 
-/// Driver models, Windows only.
+#[cfg(feature = "serde-human")]
+serde_human!(MemoryLocation {
+    L1Cache,
+    L2Cache,
+    Device,
+    RegisterFile,
+    Texture,
+    Shared,
+    Cbu,
+    SRAM
+});
+
+/// Driver models.
+///
+/// Only Windows has driver models; see `Device.driver_model_or_none()`.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlDriverModel_enum")]
-#[cfg(target_os = "windows")]
 pub enum DriverModel {
     /// GPU treated as a display device.
     #[wrap(c_variant = "NVML_DRIVER_WDDM")]
@@ -241,6 +340,9 @@ pub enum DriverModel {
     WDM,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(DriverModel { WDDM, WDM });
+
 /**
 GPU operation mode.
 
@@ -249,7 +351,10 @@ by disabling GPU features. Each mode is designed to meet specific needs.
 */
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlGom_enum")]
 pub enum OperationMode {
     /// Everything is enabled and running at full speed.
@@ -264,10 +369,20 @@ pub enum OperationMode {
     LowDP,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(OperationMode {
+    AllOn,
+    Compute,
+    LowDP
+});
+
 /// Available infoROM objects.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlInforomObject_enum")]
 pub enum InfoRom {
     /// An object defined by OEM.
@@ -281,11 +396,20 @@ pub enum InfoRom {
     Power,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(InfoRom { OEM, ECC, Power });
+
 /// Represents the queryable PCIe utilization counters (in bytes). 1KB
 /// granularity.
+///
+/// NVML does not expose a TLP count counter; `NVML_PCIE_UTIL_COUNT` is only
+/// the number of counters and is rejected by `Device.pcie_throughput()`.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlPcieUtilCounter_enum")]
 pub enum PcieUtilCounter {
     #[wrap(c_variant = "NVML_PCIE_UTIL_TX_BYTES")]
@@ -294,6 +418,9 @@ pub enum PcieUtilCounter {
     Receive,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(PcieUtilCounter { Send, Receive });
+
 /**
 Allowed performance states.
 
@@ -306,7 +433,10 @@ Value    Performance
 */
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlPStates_enum")]
 pub enum PerformanceState {
     /// Maximum performance.
@@ -348,10 +478,16 @@ pub enum PerformanceState {
     Unknown,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(PerformanceState { Zero => "P0", One => "P1", Two => "P2", Three => "P3", Four => "P4", Five => "P5", Six => "P6", Seven => "P7", Eight => "P8", Nine => "P9", Ten => "P10", Eleven => "P11", Twelve => "P12", Thirteen => "P13", Fourteen => "P14", Fifteen => "P15", Unknown });
+
 /// Causes for page retirement.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlPageRetirementCause_enum")]
 pub enum RetirementCause {
     /// Page was retired due to multiple single bit ECC errors.
@@ -362,10 +498,19 @@ pub enum RetirementCause {
     DoubleBitEccError,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(RetirementCause {
+    MultipleSingleBitEccErrors,
+    DoubleBitEccError
+});
+
 /// Possible types of sampling events.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlSamplingType_enum")]
 pub enum Sampling {
     /// Total power drawn by GPU.
@@ -393,9 +538,23 @@ pub enum Sampling {
     MemoryClock,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(Sampling {
+    Power,
+    GpuUtilization,
+    MemoryUtilization,
+    EncoderUtilization,
+    DecoderUtilization,
+    ProcessorClock,
+    MemoryClock
+});
+
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlTemperatureSensors_enum")]
 pub enum TemperatureSensor {
     /// Sensor for the GPU die.
@@ -403,9 +562,15 @@ pub enum TemperatureSensor {
     Gpu,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(TemperatureSensor { Gpu });
+
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlTemperatureThresholds_enum")]
 pub enum TemperatureThreshold {
     /// Temperature at which the GPU will shut down for hardware protection.
@@ -420,12 +585,35 @@ pub enum TemperatureThreshold {
     /// GPU temperature at which the GPU can be throttled below the base clock.
     #[wrap(c_variant = "NVML_TEMPERATURE_THRESHOLD_GPU_MAX")]
     GpuMax,
+    /// Minimum GPU temperature that can be set as the acoustic threshold.
+    #[wrap(c_variant = "NVML_TEMPERATURE_THRESHOLD_ACOUSTIC_MIN")]
+    AcousticMin,
+    /// GPU temperature that is currently set as the acoustic threshold.
+    #[wrap(c_variant = "NVML_TEMPERATURE_THRESHOLD_ACOUSTIC_CURR")]
+    AcousticCurrent,
+    /// Maximum GPU temperature that can be set as the acoustic threshold.
+    #[wrap(c_variant = "NVML_TEMPERATURE_THRESHOLD_ACOUSTIC_MAX")]
+    AcousticMax,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(TemperatureThreshold {
+    Shutdown,
+    Slowdown,
+    MemoryMax,
+    GpuMax,
+    AcousticMin,
+    AcousticCurrent,
+    AcousticMax
+});
+
 /// Level relationships within a system between two GPUs.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlGpuLevel_enum")]
 pub enum TopologyLevel {
     /// e.g. Tesla K80.
@@ -453,20 +641,38 @@ pub enum TopologyLevel {
     System,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(TopologyLevel {
+    Internal,
+    Single,
+    Multiple,
+    HostBridge,
+    Node,
+    System
+});
+
 /// Types of performance policy for which violation times can be queried.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlPerfPolicyType_enum")]
 pub enum PerformancePolicy {
+    /// Power capping.
     #[wrap(c_variant = "NVML_PERF_POLICY_POWER")]
     Power,
+    /// Thermal capping.
     #[wrap(c_variant = "NVML_PERF_POLICY_THERMAL")]
     Thermal,
+    /// Holding clocks in step with the other GPUs in a sync boost group.
     #[wrap(c_variant = "NVML_PERF_POLICY_SYNC_BOOST")]
     SyncBoost,
+    /// Board power or current limit.
     #[wrap(c_variant = "NVML_PERF_POLICY_BOARD_LIMIT")]
     BoardLimit,
+    /// Low utilization.
     #[wrap(c_variant = "NVML_PERF_POLICY_LOW_UTILIZATION")]
     LowUtilization,
     /// Board reliability limit.
@@ -481,12 +687,27 @@ pub enum PerformancePolicy {
     TotalBaseClocks,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(PerformancePolicy {
+    Power,
+    Thermal,
+    SyncBoost,
+    BoardLimit,
+    LowUtilization,
+    Reliability,
+    TotalAppClocks,
+    TotalBaseClocks
+});
+
 /// `ExclusiveProcess` was added in CUDA 4.0. Earlier CUDA versions supported a
 /// single exclusive mode, which is equivalent to `ExclusiveThread` in CUDA 4.0
 /// and beyond.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlComputeMode_enum")]
 pub enum ComputeMode {
     /// Multiple contexts per device.
@@ -506,10 +727,21 @@ pub enum ComputeMode {
     ExclusiveProcess,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(ComputeMode {
+    Default,
+    ExclusiveThread,
+    Prohibited,
+    ExclusiveProcess
+});
+
 /// P2P capability index status.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlGpuP2PStatus_enum")]
 pub enum P2pStatus {
     #[wrap(c_variant = "NVML_P2P_STATUS_OK")]
@@ -528,9 +760,23 @@ pub enum P2pStatus {
     Unknown,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(P2pStatus {
+    Ok,
+    ChipsetNotSupported,
+    GpuNotSupported,
+    IohTopologyNotSupported,
+    DisabledByRegkey,
+    NotSupported,
+    Unknown
+});
+
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlGpuP2PCapsIndex_enum")]
 pub enum P2pCapabilitiesIndex {
     #[wrap(c_variant = "NVML_P2P_CAPS_INDEX_READ")]
@@ -547,10 +793,23 @@ pub enum P2pCapabilitiesIndex {
     Unknown,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(P2pCapabilitiesIndex {
+    Read,
+    Write,
+    NvLink,
+    Atomics,
+    Prop,
+    Unknown
+});
+
 /// Represents types for returned sample values.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlValueType_enum")]
 pub enum SampleValueType {
     #[wrap(c_variant = "NVML_VALUE_TYPE_DOUBLE")]
@@ -565,9 +824,21 @@ pub enum SampleValueType {
     SignedLongLong,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(SampleValueType {
+    Double,
+    UnsignedInt,
+    UnsignedLong,
+    UnsignedLongLong,
+    SignedLongLong
+});
+
 /// Represents encoder types that capacity can be queried for.
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlEncoderQueryType_enum")]
 pub enum EncoderType {
     #[wrap(c_variant = "NVML_ENCODER_QUERY_H264")]
@@ -576,11 +847,17 @@ pub enum EncoderType {
     HEVC,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(EncoderType { H264, HEVC });
+
 /// The type of a frame buffer capture session
 ///
 /// NVIDIA doesn't document the variants beyond their names.
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlFBCSessionType_enum")]
 pub enum FbcSessionType {
     #[wrap(c_variant = "NVML_FBC_SESSION_TYPE_UNKNOWN")]
@@ -595,9 +872,21 @@ pub enum FbcSessionType {
     HwEnc,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(FbcSessionType {
+    Unknown,
+    ToSys,
+    Cuda,
+    Vid,
+    HwEnc
+});
+
 /// Options to pass to [`crate::Device::remove()`].
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlDetachGpuState_enum")]
 pub enum DetachGpuState {
     #[wrap(c_variant = "NVML_DETACH_GPU_KEEP")]
@@ -606,9 +895,15 @@ pub enum DetachGpuState {
     Remove,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(DetachGpuState { Keep, Remove });
+
 /// Options to pass to [`crate::Device::remove()`].
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlPcieLinkState_enum")]
 pub enum PcieLinkState {
     #[wrap(c_variant = "NVML_PCIE_LINK_KEEP")]
@@ -617,9 +912,15 @@ pub enum PcieLinkState {
     ShutDown,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(PcieLinkState { Keep, ShutDown });
+
 /// Clock limit IDs for use with [`crate::Device::set_gpu_locked_clocks()`].
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlClockLimitId_enum")]
 pub enum ClockLimitId {
     /// Bound clock speed by the TDP of the device.
@@ -629,3 +930,175 @@ pub enum ClockLimitId {
     #[wrap(c_variant = "NVML_CLOCK_LIMIT_ID_UNLIMITED")]
     Unlimited,
 }
+
+#[cfg(feature = "serde-human")]
+serde_human!(ClockLimitId { Tdp, Unlimited });
+
+/// vGPU capabilities that can be queried with [`crate::Device::vgpu_capability()`].
+#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
+#[wrap(c_enum = "nvmlDeviceVgpuCapability_enum")]
+pub enum DeviceVgpuCapability {
+    /// Fractional vGPU profiles on this device can be used in multi-vGPU
+    /// configurations.
+    #[wrap(c_variant = "NVML_DEVICE_VGPU_CAP_FRACTIONAL_MULTI_VGPU")]
+    FractionalMultiVgpu,
+    /// Concurrent execution of timesliced vGPU profiles of differing types is
+    /// supported.
+    #[wrap(c_variant = "NVML_DEVICE_VGPU_CAP_HETEROGENEOUS_TIMESLICE_PROFILES")]
+    HeterogeneousTimesliceProfiles,
+    /// Concurrent execution of timesliced vGPU profiles of differing
+    /// framebuffer sizes is supported.
+    #[wrap(c_variant = "NVML_DEVICE_VGPU_CAP_HETEROGENEOUS_TIMESLICE_SIZES")]
+    HeterogeneousTimesliceSizes,
+    /// The device buffer read bandwidth is known.
+    #[wrap(c_variant = "NVML_DEVICE_VGPU_CAP_READ_DEVICE_BUFFER_BW")]
+    ReadDeviceBufferBandwidth,
+    /// The device buffer write bandwidth is known.
+    #[wrap(c_variant = "NVML_DEVICE_VGPU_CAP_WRITE_DEVICE_BUFFER_BW")]
+    WriteDeviceBufferBandwidth,
+}
+
+#[cfg(feature = "serde-human")]
+serde_human!(DeviceVgpuCapability {
+    FractionalMultiVgpu,
+    HeterogeneousTimesliceProfiles,
+    HeterogeneousTimesliceSizes,
+    ReadDeviceBufferBandwidth,
+    WriteDeviceBufferBandwidth
+});
+
+#[cfg(test)]
+mod test {
+    use super::{Brand, PcieUtilCounter, PerformancePolicy};
+    use crate::error::NvmlError;
+    use crate::ffi::bindings::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn brand_newer_variants() {
+        let pairs = [
+            (nvmlBrandType_enum_NVML_BRAND_NVS, Brand::NVS),
+            (
+                nvmlBrandType_enum_NVML_BRAND_NVIDIA_CLOUD_GAMING,
+                Brand::CloudGaming,
+            ),
+            (nvmlBrandType_enum_NVML_BRAND_QUADRO_RTX, Brand::QuadroRTX),
+            (nvmlBrandType_enum_NVML_BRAND_NVIDIA_RTX, Brand::NvidiaRTX),
+            (nvmlBrandType_enum_NVML_BRAND_NVIDIA, Brand::Nvidia),
+            (nvmlBrandType_enum_NVML_BRAND_GEFORCE_RTX, Brand::GeForceRTX),
+            (nvmlBrandType_enum_NVML_BRAND_TITAN_RTX, Brand::TitanRTX),
+        ];
+
+        for (c_brand, brand) in pairs.iter() {
+            assert_eq!(&Brand::try_from(*c_brand).unwrap(), brand);
+            assert_eq!(brand.as_c(), *c_brand);
+        }
+    }
+
+    #[test]
+    fn brand_vgaming_is_cloud_gaming() {
+        assert_eq!(
+            Brand::try_from(nvmlBrandType_enum_NVML_BRAND_NVIDIA_VGAMING).unwrap(),
+            Brand::CloudGaming
+        );
+    }
+
+    // `Device.brand()` maps this error to `Brand::Unknown`
+    #[test]
+    fn brand_out_of_range() {
+        match Brand::try_from(nvmlBrandType_enum_NVML_BRAND_COUNT) {
+            Err(NvmlError::UnexpectedVariant(v)) => {
+                assert_eq!(v, nvmlBrandType_enum_NVML_BRAND_COUNT)
+            }
+            other => panic!("expected `UnexpectedVariant`, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn pcie_util_counter_round_trip() {
+        let pairs = [
+            (
+                nvmlPcieUtilCounter_enum_NVML_PCIE_UTIL_TX_BYTES,
+                PcieUtilCounter::Send,
+            ),
+            (
+                nvmlPcieUtilCounter_enum_NVML_PCIE_UTIL_RX_BYTES,
+                PcieUtilCounter::Receive,
+            ),
+        ];
+
+        for (c_counter, counter) in pairs.iter() {
+            assert_eq!(&PcieUtilCounter::try_from(*c_counter).unwrap(), counter);
+            assert_eq!(counter.as_c(), *c_counter);
+        }
+    }
+
+    // `NVML_PCIE_UTIL_COUNT` is the number of counters, not a counter
+    #[test]
+    fn pcie_util_counter_count_is_not_a_counter() {
+        match PcieUtilCounter::try_from(nvmlPcieUtilCounter_enum_NVML_PCIE_UTIL_COUNT) {
+            Err(NvmlError::UnexpectedVariant(v)) => {
+                assert_eq!(v, nvmlPcieUtilCounter_enum_NVML_PCIE_UTIL_COUNT)
+            }
+            other => panic!("expected `UnexpectedVariant`, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn performance_policy_round_trip() {
+        let pairs = [
+            (
+                nvmlPerfPolicyType_enum_NVML_PERF_POLICY_POWER,
+                PerformancePolicy::Power,
+            ),
+            (
+                nvmlPerfPolicyType_enum_NVML_PERF_POLICY_THERMAL,
+                PerformancePolicy::Thermal,
+            ),
+            (
+                nvmlPerfPolicyType_enum_NVML_PERF_POLICY_SYNC_BOOST,
+                PerformancePolicy::SyncBoost,
+            ),
+            (
+                nvmlPerfPolicyType_enum_NVML_PERF_POLICY_BOARD_LIMIT,
+                PerformancePolicy::BoardLimit,
+            ),
+            (
+                nvmlPerfPolicyType_enum_NVML_PERF_POLICY_LOW_UTILIZATION,
+                PerformancePolicy::LowUtilization,
+            ),
+            (
+                nvmlPerfPolicyType_enum_NVML_PERF_POLICY_RELIABILITY,
+                PerformancePolicy::Reliability,
+            ),
+            (
+                nvmlPerfPolicyType_enum_NVML_PERF_POLICY_TOTAL_APP_CLOCKS,
+                PerformancePolicy::TotalAppClocks,
+            ),
+            (
+                nvmlPerfPolicyType_enum_NVML_PERF_POLICY_TOTAL_BASE_CLOCKS,
+                PerformancePolicy::TotalBaseClocks,
+            ),
+        ];
+
+        for (c_policy, policy) in pairs.iter() {
+            assert_eq!(&PerformancePolicy::try_from(*c_policy).unwrap(), policy);
+            assert_eq!(policy.as_c(), *c_policy);
+        }
+    }
+
+    // `NVML_PERF_POLICY_COUNT` is the number of policies, not a policy
+    #[test]
+    fn performance_policy_count_is_not_a_policy() {
+        match PerformancePolicy::try_from(nvmlPerfPolicyType_enum_NVML_PERF_POLICY_COUNT) {
+            Err(NvmlError::UnexpectedVariant(v)) => {
+                assert_eq!(v, nvmlPerfPolicyType_enum_NVML_PERF_POLICY_COUNT)
+            }
+            other => panic!("expected `UnexpectedVariant`, got {:?}", other),
+        }
+    }
+}
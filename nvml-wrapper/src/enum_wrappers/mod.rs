@@ -1,6 +1,53 @@
 use crate::error::NvmlError;
 use crate::ffi::bindings::*;
 
+/// Implements `Serialize` and `Deserialize` for an enum wrapper so that it is
+/// represented by the name of its variant in every data format.
+///
+/// Variants are named after their identifier unless a name is given explicitly.
+#[cfg(feature = "serde-human")]
+macro_rules! serde_human {
+    ($ty:ident { $($variant:ident $(=> $name:literal)?),+ $(,)? }) => {
+        impl serde::Serialize for $ty {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                let name = match self {
+                    $($ty::$variant => serde_human!(@name $variant $($name)?),)+
+                };
+
+                serializer.serialize_str(name)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $ty {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                const VARIANTS: &[&str] = &[$(serde_human!(@name $variant $($name)?)),+];
+
+                let name = String::deserialize(deserializer)?;
+
+                $(
+                    if name == serde_human!(@name $variant $($name)?) {
+                        return Ok($ty::$variant);
+                    }
+                )+
+
+                Err(serde::de::Error::unknown_variant(&name, VARIANTS))
+            }
+        }
+    };
+    (@name $variant:ident) => {
+        stringify!($variant)
+    };
+    (@name $variant:ident $name:literal) => {
+        $name
+    };
+}
+
 pub mod device;
 pub mod nv_link;
 pub mod unit;
@@ -20,3 +67,56 @@ pub fn state_from_bool(enabled: bool) -> nvmlEnableState_t {
         nvmlEnableState_enum_NVML_FEATURE_DISABLED
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+    use super::device::{Clock, PerformanceState};
+    use super::unit::LedColor;
+    use serde_test::{assert_tokens, Token};
+
+    #[cfg(not(feature = "serde-human"))]
+    #[test]
+    fn serde_round_trip_compact() {
+        assert_tokens(
+            &PerformanceState::Zero,
+            &[Token::UnitVariant {
+                name: "PerformanceState",
+                variant: "Zero",
+            }],
+        );
+        assert_tokens(
+            &Clock::SM,
+            &[Token::UnitVariant {
+                name: "Clock",
+                variant: "SM",
+            }],
+        );
+        assert_tokens(
+            &LedColor::Amber,
+            &[Token::UnitVariant {
+                name: "LedColor",
+                variant: "Amber",
+            }],
+        );
+    }
+
+    #[cfg(feature = "serde-human")]
+    #[test]
+    fn serde_round_trip_human() {
+        assert_tokens(&PerformanceState::Zero, &[Token::Str("P0")]);
+        assert_tokens(&PerformanceState::Fifteen, &[Token::Str("P15")]);
+        assert_tokens(&PerformanceState::Unknown, &[Token::Str("Unknown")]);
+        assert_tokens(&Clock::SM, &[Token::Str("SM")]);
+        assert_tokens(&LedColor::Amber, &[Token::Str("Amber")]);
+    }
+
+    #[cfg(feature = "serde-human")]
+    #[test]
+    fn serde_human_unknown_variant() {
+        serde_test::assert_de_tokens_error::<PerformanceState>(
+            &[Token::Str("Zero")],
+            "unknown variant `Zero`, expected one of `P0`, `P1`, `P2`, `P3`, `P4`, \
+             `P5`, `P6`, `P7`, `P8`, `P9`, `P10`, `P11`, `P12`, `P13`, `P14`, `P15`, `Unknown`",
+        );
+    }
+}
@@ -1,13 +1,16 @@
 use crate::error::NvmlError;
 use crate::ffi::bindings::*;
-#[cfg(feature = "serde")]
+#[cfg(all(feature = "serde", not(feature = "serde-human")))]
 use serde_derive::{Deserialize, Serialize};
 use wrapcenum_derive::EnumWrapper;
 
 /// Represents the NvLink utilization counter packet units.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlNvLinkUtilizationCountUnits_enum")]
 pub enum UtilizationCountUnit {
     #[wrap(c_variant = "NVML_NVLINK_COUNTER_UNIT_CYCLES")]
@@ -18,10 +21,20 @@ pub enum UtilizationCountUnit {
     Bytes,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(UtilizationCountUnit {
+    Cycles,
+    Packets,
+    Bytes
+});
+
 /// Represents queryable NvLink capabilities.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlNvLinkCapability_enum")]
 pub enum Capability {
     /// P2P over NVLink is supported.
@@ -44,10 +57,23 @@ pub enum Capability {
     ValidLink,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(Capability {
+    P2p,
+    SysMemAccess,
+    P2pAtomics,
+    SysMemAtomics,
+    SliBridge,
+    ValidLink
+});
+
 /// Represents queryable NvLink error counters.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlNvLinkErrorCounter_enum")]
 pub enum ErrorCounter {
     /// Data link transmit replay error counter.
@@ -63,3 +89,41 @@ pub enum ErrorCounter {
     #[wrap(c_variant = "NVML_NVLINK_ERROR_DL_CRC_DATA")]
     DlCrcData,
 }
+
+#[cfg(feature = "serde-human")]
+serde_human!(ErrorCounter {
+    DlReplay,
+    DlRecovery,
+    DlCrcFlit,
+    DlCrcData
+});
+
+/// The type of device on the other end of an NvLink.
+// Checked against local
+#[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
+#[wrap(c_enum = "nvmlIntNvLinkDeviceType_enum")]
+pub enum RemoteDeviceType {
+    /// Another GPU.
+    #[wrap(c_variant = "NVML_NVLINK_DEVICE_TYPE_GPU")]
+    Gpu,
+    /// An IBM NPU (the NvLink interface on POWER CPUs).
+    #[wrap(c_variant = "NVML_NVLINK_DEVICE_TYPE_IBMNPU")]
+    IbmNpu,
+    /// An NvSwitch.
+    #[wrap(c_variant = "NVML_NVLINK_DEVICE_TYPE_SWITCH")]
+    Switch,
+    #[wrap(c_variant = "NVML_NVLINK_DEVICE_TYPE_UNKNOWN")]
+    Unknown,
+}
+
+#[cfg(feature = "serde-human")]
+serde_human!(RemoteDeviceType {
+    Gpu,
+    IbmNpu,
+    Switch,
+    Unknown
+});
@@ -1,13 +1,16 @@
 use crate::error::NvmlError;
 use crate::ffi::bindings::*;
-#[cfg(feature = "serde")]
+#[cfg(all(feature = "serde", not(feature = "serde-human")))]
 use serde_derive::{Deserialize, Serialize};
 use wrapcenum_derive::EnumWrapper;
 
 /// Unit fan state.
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlFanState_enum")]
 pub enum FanState {
     /// Working properly
@@ -17,9 +20,15 @@ pub enum FanState {
     Failed,
 }
 
+#[cfg(feature = "serde-human")]
+serde_human!(FanState { Normal, Failed });
+
 // Checked against local
 #[derive(EnumWrapper, Debug, Clone, Eq, PartialEq, Hash)]
-#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    all(feature = "serde", not(feature = "serde-human")),
+    derive(Serialize, Deserialize)
+)]
 #[wrap(c_enum = "nvmlLedColor_enum")]
 pub enum LedColor {
     /// Used to indicate good health.
@@ -29,3 +38,6 @@ pub enum LedColor {
     #[wrap(c_variant = "NVML_LED_COLOR_AMBER")]
     Amber,
 }
+
+#[cfg(feature = "serde-human")]
+serde_human!(LedColor { Green, Amber });
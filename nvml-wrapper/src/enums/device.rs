@@ -2,7 +2,7 @@ use std::convert::TryFrom;
 use std::fmt::Display;
 use std::os::raw::c_uint;
 
-use crate::enum_wrappers::device::{ClockLimitId, SampleValueType};
+use crate::enum_wrappers::device::{BridgeChip, ClockLimitId, SampleValueType};
 use crate::error::NvmlError;
 use crate::ffi::bindings::*;
 #[cfg(feature = "serde")]
@@ -26,6 +26,44 @@ impl From<u32> for FirmwareVersion {
     }
 }
 
+impl Display for FirmwareVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unavailable => f.write_str("unavailable"),
+            Self::Version(v) => write!(f, "{:#x}", v),
+        }
+    }
+}
+
+/// Represents possible variants for the type of a bridge chip.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BridgeChipType {
+    /// A bridge chip type known to this wrapper.
+    Known(BridgeChip),
+    /// A bridge chip type that this wrapper does not know about (yet).
+    Unknown(u32),
+}
+
+impl From<nvmlBridgeChipType_t> for BridgeChipType {
+    fn from(value: nvmlBridgeChipType_t) -> Self {
+        match BridgeChip::try_from(value) {
+            Ok(chip) => Self::Known(chip),
+            Err(_) => Self::Unknown(value),
+        }
+    }
+}
+
+impl Display for BridgeChipType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Known(BridgeChip::PLX) => f.write_str("PLX"),
+            Self::Known(BridgeChip::BRO4) => f.write_str("BRO4"),
+            Self::Unknown(v) => write!(f, "unknown ({})", v),
+        }
+    }
+}
+
 /// Represents possible variants for used GPU memory.
 // Checked
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -77,6 +115,17 @@ impl SampleValue {
             }
         }
     }
+
+    // Converts an integer sample to a `u32`, returning `Unknown` for a
+    // floating point sample or one that doesn't fit.
+    pub(crate) fn to_u32(&self) -> Result<u32, NvmlError> {
+        match *self {
+            SampleValue::U32(v) => Ok(v),
+            SampleValue::U64(v) => u32::try_from(v).map_err(|_| NvmlError::Unknown),
+            SampleValue::I64(v) => u32::try_from(v).map_err(|_| NvmlError::Unknown),
+            SampleValue::F64(_) => Err(NvmlError::Unknown),
+        }
+    }
 }
 
 /// Represents different types of sample values.
@@ -345,3 +394,288 @@ impl TryFrom<c_uint> for PcieLinkMaxSpeed {
         }
     }
 }
+
+/// A PCIe generation, as numbered by [`crate::Device::current_pcie_link_gen()`].
+///
+/// Knows the per-lane transfer rate and line encoding of each generation so
+/// that the usable bandwidth of a link can be worked out. See
+/// [`crate::Device::pcie_bandwidth_gbps()`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum PcieGeneration {
+    Gen1,
+    Gen2,
+    Gen3,
+    Gen4,
+    Gen5,
+    Gen6,
+}
+
+impl PcieGeneration {
+    /// Returns the generation number for the given enum variant.
+    pub fn as_integer(&self) -> u32 {
+        match self {
+            Self::Gen1 => 1,
+            Self::Gen2 => 2,
+            Self::Gen3 => 3,
+            Self::Gen4 => 4,
+            Self::Gen5 => 5,
+            Self::Gen6 => 6,
+        }
+    }
+
+    /// The raw transfer rate of a single lane, in GT/s.
+    pub fn transfer_rate_gts(&self) -> f64 {
+        match self {
+            Self::Gen1 => 2.5,
+            Self::Gen2 => 5.0,
+            Self::Gen3 => 8.0,
+            Self::Gen4 => 16.0,
+            Self::Gen5 => 32.0,
+            Self::Gen6 => 64.0,
+        }
+    }
+
+    /// The fraction of transferred bits that carry data rather than line
+    /// encoding overhead (8b/10b up to gen 2, 128b/130b for gens 3 to 5).
+    ///
+    /// Gen 6 has no line encoding and sends data in 256-byte FLITs instead,
+    /// 14 bytes of which are CRC and FEC, so its efficiency is 242/256.
+    pub fn encoding_efficiency(&self) -> f64 {
+        match self {
+            Self::Gen1 | Self::Gen2 => 8.0 / 10.0,
+            Self::Gen3 | Self::Gen4 | Self::Gen5 => 128.0 / 130.0,
+            Self::Gen6 => 242.0 / 256.0,
+        }
+    }
+
+    /// The usable bandwidth of a single lane in one direction, in Gbit/s.
+    pub fn lane_bandwidth_gbps(&self) -> f64 {
+        self.transfer_rate_gts() * self.encoding_efficiency()
+    }
+}
+
+impl TryFrom<u32> for PcieGeneration {
+    type Error = NvmlError;
+
+    fn try_from(data: u32) -> Result<Self, Self::Error> {
+        match data {
+            1 => Ok(Self::Gen1),
+            2 => Ok(Self::Gen2),
+            3 => Ok(Self::Gen3),
+            4 => Ok(Self::Gen4),
+            5 => Ok(Self::Gen5),
+            6 => Ok(Self::Gen6),
+            _ => Err(NvmlError::UnexpectedVariant(data)),
+        }
+    }
+}
+
+/// The target of a thermal sensor, as reported by [`crate::Device::thermal_settings()`].
+// TODO: technically this is an "enum wrapper" but the type on the C side isn't
+// an enum
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ThermalTarget {
+    None,
+    /// The GPU core temperature.
+    Gpu,
+    /// The memory temperature.
+    Memory,
+    /// The power supply temperature.
+    PowerSupply,
+    /// The board ambient temperature.
+    Board,
+    VcdBoard,
+    VcdInlet,
+    VcdOutlet,
+    All,
+    Unknown,
+}
+
+impl ThermalTarget {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> nvmlThermalTarget_t {
+        match *self {
+            Self::None => nvmlThermalTarget_t_NVML_THERMAL_TARGET_NONE,
+            Self::Gpu => nvmlThermalTarget_t_NVML_THERMAL_TARGET_GPU,
+            Self::Memory => nvmlThermalTarget_t_NVML_THERMAL_TARGET_MEMORY,
+            Self::PowerSupply => nvmlThermalTarget_t_NVML_THERMAL_TARGET_POWER_SUPPLY,
+            Self::Board => nvmlThermalTarget_t_NVML_THERMAL_TARGET_BOARD,
+            Self::VcdBoard => nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_BOARD,
+            Self::VcdInlet => nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_INLET,
+            Self::VcdOutlet => nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_OUTLET,
+            Self::All => nvmlThermalTarget_t_NVML_THERMAL_TARGET_ALL,
+            Self::Unknown => nvmlThermalTarget_t_NVML_THERMAL_TARGET_UNKNOWN,
+        }
+    }
+}
+
+impl TryFrom<nvmlThermalTarget_t> for ThermalTarget {
+    type Error = NvmlError;
+
+    fn try_from(data: nvmlThermalTarget_t) -> Result<Self, Self::Error> {
+        match data {
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_NONE => Ok(Self::None),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_GPU => Ok(Self::Gpu),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_MEMORY => Ok(Self::Memory),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_POWER_SUPPLY => Ok(Self::PowerSupply),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_BOARD => Ok(Self::Board),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_BOARD => Ok(Self::VcdBoard),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_INLET => Ok(Self::VcdInlet),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_VCD_OUTLET => Ok(Self::VcdOutlet),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_ALL => Ok(Self::All),
+            nvmlThermalTarget_t_NVML_THERMAL_TARGET_UNKNOWN => Ok(Self::Unknown),
+            _ => Err(NvmlError::UnexpectedVariant(data as u32)),
+        }
+    }
+}
+
+/// The controller of a thermal sensor, as reported by [`crate::Device::thermal_settings()`].
+// TODO: technically this is an "enum wrapper" but the type on the C side isn't
+// an enum
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ThermalController {
+    /// No thermal controller.
+    None,
+    /// The GPU's internal thermal controller.
+    GpuInternal,
+    Adm1032,
+    Adt7461,
+    Max6649,
+    Max1617,
+    Lm99,
+    Lm89,
+    Lm64,
+    G781,
+    Adt7473,
+    SbMax6649,
+    VbiosEvt,
+    Os,
+    NvSysConCanoas,
+    NvSysConE551,
+    Max6649R,
+    Adt7473S,
+    Unknown,
+}
+
+impl ThermalController {
+    /// Returns the C constant equivalent for the given Rust enum variant.
+    pub fn as_c(&self) -> nvmlThermalController_t {
+        match *self {
+            Self::None => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NONE,
+            Self::GpuInternal => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_GPU_INTERNAL,
+            Self::Adm1032 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADM1032,
+            Self::Adt7461 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7461,
+            Self::Max6649 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX6649,
+            Self::Max1617 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX1617,
+            Self::Lm99 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM99,
+            Self::Lm89 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM89,
+            Self::Lm64 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM64,
+            Self::G781 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_G781,
+            Self::Adt7473 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7473,
+            Self::SbMax6649 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_SBMAX6649,
+            Self::VbiosEvt => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_VBIOSEVT,
+            Self::Os => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_OS,
+            Self::NvSysConCanoas => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NVSYSCON_CANOAS,
+            Self::NvSysConE551 => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NVSYSCON_E551,
+            Self::Max6649R => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX6649R,
+            Self::Adt7473S => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7473S,
+            Self::Unknown => nvmlThermalController_t_NVML_THERMAL_CONTROLLER_UNKNOWN,
+        }
+    }
+}
+
+impl TryFrom<nvmlThermalController_t> for ThermalController {
+    type Error = NvmlError;
+
+    fn try_from(data: nvmlThermalController_t) -> Result<Self, Self::Error> {
+        match data {
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NONE => Ok(Self::None),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_GPU_INTERNAL => Ok(Self::GpuInternal),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADM1032 => Ok(Self::Adm1032),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7461 => Ok(Self::Adt7461),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX6649 => Ok(Self::Max6649),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX1617 => Ok(Self::Max1617),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM99 => Ok(Self::Lm99),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM89 => Ok(Self::Lm89),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_LM64 => Ok(Self::Lm64),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_G781 => Ok(Self::G781),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7473 => Ok(Self::Adt7473),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_SBMAX6649 => Ok(Self::SbMax6649),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_VBIOSEVT => Ok(Self::VbiosEvt),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_OS => Ok(Self::Os),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NVSYSCON_CANOAS => {
+                Ok(Self::NvSysConCanoas)
+            }
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_NVSYSCON_E551 => Ok(Self::NvSysConE551),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_MAX6649R => Ok(Self::Max6649R),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_ADT7473S => Ok(Self::Adt7473S),
+            nvmlThermalController_t_NVML_THERMAL_CONTROLLER_UNKNOWN => Ok(Self::Unknown),
+            _ => Err(NvmlError::UnexpectedVariant(data as u32)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PcieGeneration, SampleValue};
+    use crate::error::NvmlError;
+
+    use std::convert::TryFrom;
+
+    #[test]
+    fn sample_value_to_u32() {
+        assert_eq!(SampleValue::U32(7).to_u32().unwrap(), 7);
+        assert_eq!(SampleValue::U64(70).to_u32().unwrap(), 70);
+        assert_eq!(SampleValue::I64(700).to_u32().unwrap(), 700);
+
+        assert!(matches!(
+            SampleValue::U64(u64::from(u32::MAX) + 1).to_u32(),
+            Err(NvmlError::Unknown)
+        ));
+        assert!(matches!(
+            SampleValue::I64(-1).to_u32(),
+            Err(NvmlError::Unknown)
+        ));
+        assert!(matches!(
+            SampleValue::F64(1.0).to_u32(),
+            Err(NvmlError::Unknown)
+        ));
+    }
+
+    #[test]
+    fn pcie_generation_round_trip() {
+        for gen in 1..=6 {
+            assert_eq!(PcieGeneration::try_from(gen).unwrap().as_integer(), gen);
+        }
+
+        assert!(matches!(
+            PcieGeneration::try_from(0),
+            Err(NvmlError::UnexpectedVariant(0))
+        ));
+        assert!(matches!(
+            PcieGeneration::try_from(7),
+            Err(NvmlError::UnexpectedVariant(7))
+        ));
+    }
+
+    #[test]
+    fn pcie_generation_transfer_rates() {
+        let rates: Vec<f64> = (1..=6)
+            .map(|gen| PcieGeneration::try_from(gen).unwrap().transfer_rate_gts())
+            .collect();
+
+        assert_eq!(rates, vec![2.5, 5.0, 8.0, 16.0, 32.0, 64.0]);
+    }
+
+    #[test]
+    fn pcie_generation_lane_bandwidth() {
+        assert_eq!(PcieGeneration::Gen1.lane_bandwidth_gbps(), 2.0);
+        assert_eq!(PcieGeneration::Gen2.lane_bandwidth_gbps(), 4.0);
+        assert!((PcieGeneration::Gen3.lane_bandwidth_gbps() - 7.877).abs() < 0.001);
+        assert!((PcieGeneration::Gen4.lane_bandwidth_gbps() * 16.0 - 252.062).abs() < 0.001);
+        assert_eq!(PcieGeneration::Gen6.lane_bandwidth_gbps(), 60.5);
+    }
+}
@@ -1,5 +1,7 @@
+use crate::error::NvmlError;
 #[cfg(feature = "serde")]
 use serde_derive::{Deserialize, Serialize};
+use std::convert::TryFrom;
 
 /// Used to specify the counter in `NvLink.set_utilization_control_for()`
 ///
@@ -11,3 +13,51 @@ pub enum Counter {
     Zero = 0,
     One = 1,
 }
+
+impl Counter {
+    /// Every `Counter`, in index order.
+    pub fn all() -> &'static [Counter] {
+        &[Counter::Zero, Counter::One]
+    }
+}
+
+/// Converts a raw counter index, as used by NVML, into a `Counter`.
+///
+/// Returns `InvalidArg` for any index other than 0 or 1, so that an
+/// out-of-range index is caught before it reaches NVML.
+impl TryFrom<u32> for Counter {
+    type Error = NvmlError;
+
+    fn try_from(data: u32) -> Result<Self, Self::Error> {
+        match data {
+            0 => Ok(Counter::Zero),
+            1 => Ok(Counter::One),
+            _ => Err(NvmlError::InvalidArg),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Counter;
+    use crate::error::NvmlError;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn all_counters_in_index_order() {
+        assert_eq!(Counter::all(), &[Counter::Zero, Counter::One]);
+
+        for (index, counter) in Counter::all().iter().enumerate() {
+            assert_eq!(counter.clone() as u32, index as u32);
+        }
+    }
+
+    #[test]
+    fn counter_from_index() {
+        for counter in Counter::all() {
+            assert_eq!(&Counter::try_from(counter.clone() as u32).unwrap(), counter);
+        }
+
+        assert!(matches!(Counter::try_from(2), Err(NvmlError::InvalidArg)));
+    }
+}
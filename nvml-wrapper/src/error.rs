@@ -1,3 +1,7 @@
+#[cfg(target_os = "linux")]
+use crate::EventSet;
+
+use crate::enum_wrappers::device::SampleValueType;
 use crate::ffi::bindings::*;
 #[cfg(feature = "serde")]
 use serde_derive::{Deserialize, Serialize};
@@ -27,6 +31,46 @@ impl From<NvmlError> for NvmlErrorWithSource {
     }
 }
 
+/**
+Returned from `Device.register_events()` when registration fails
+
+If the `EventSet` passed in to `.register_events()` is still valid after the
+failure, it is handed back in `set` so that it can be used again.
+*/
+#[cfg(target_os = "linux")]
+#[derive(Error, Debug)]
+#[error("{error}")]
+pub struct RegisterEventsError<'nvml> {
+    pub error: NvmlError,
+    pub source: Option<NvmlError>,
+    /// The set that was passed in to `.register_events()`, if it is still valid.
+    ///
+    /// This is `None` if NVML returned `Unknown`, as NVIDIA's docs say that
+    /// the set is then in an invalid state; it has been freed.
+    pub set: Option<EventSet<'nvml>>,
+}
+
+#[cfg(target_os = "linux")]
+impl From<NvmlError> for RegisterEventsError<'_> {
+    fn from(error: NvmlError) -> Self {
+        Self {
+            error,
+            source: None,
+            set: None,
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl From<RegisterEventsError<'_>> for NvmlErrorWithSource {
+    fn from(error: RegisterEventsError<'_>) -> Self {
+        Self {
+            error: error.error,
+            source: error.source,
+        }
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum NvmlError {
     #[error("could not interpret string as utf-8")]
@@ -77,6 +121,58 @@ pub enum NvmlError {
     #[error("a call to `PciInfo.try_into_c()` failed")]
     PciInfoToCFailed,
 
+    /**
+    The requested application clocks are not a supported combination.
+
+    This error is specific to this Rust wrapper. It is returned by
+    `Device.set_applications_clocks_checked()` in place of NVML's `InvalidArg`
+    and carries the closest supported combination to the one that was requested.
+    */
+    #[error(
+        "unsupported application clocks; the nearest supported combination is \
+        {nearest_mem_clock} MHz memory / {nearest_graphics_clock} MHz graphics"
+    )]
+    UnsupportedClocks {
+        nearest_mem_clock: u32,
+        nearest_graphics_clock: u32,
+    },
+
+    /**
+    The requested locked clock range falls outside of the supported range.
+
+    This error is specific to this Rust wrapper. It is returned by
+    `Device.set_mem_locked_clocks_checked()` in place of NVML's `InvalidArg`
+    and carries the range of clocks that the `Device` supports.
+    */
+    #[error(
+        "locked clock range is out of bounds; the supported range is \
+        {min_supported} MHz to {max_supported} MHz"
+    )]
+    ClocksOutOfRange {
+        min_supported: u32,
+        max_supported: u32,
+    },
+
+    /**
+    The requested device index is not less than the number of devices.
+
+    This error is specific to this Rust wrapper. It is returned by
+    `Nvml.device_by_index_checked()` and `Unit.device_by_index()` and carries
+    the number of devices that are available.
+    */
+    #[error("device index {index} is out of range; there are {count} devices")]
+    DeviceIndexOutOfRange { index: u32, count: u32 },
+
+    /**
+    Samples were not of the value type that was asked for.
+
+    This error is specific to this Rust wrapper. It is returned by
+    `Device.samples_typed()` and carries the type that NVML returned the
+    samples as.
+    */
+    #[error("samples have value type {0:?}, which does not match the requested type")]
+    SampleValueTypeMismatch(SampleValueType),
+
     #[error("NVML was not first initialized with `Nvml::init()`")]
     Uninitialized,
 
@@ -203,3 +299,51 @@ pub fn nvml_try(code: nvmlReturn_t) -> Result<(), NvmlError> {
 pub fn nvml_sym<'a, T>(sym: Result<&'a T, &libloading::Error>) -> Result<&'a T, NvmlError> {
     sym.map_err(|e| NvmlError::FailedToLoadSymbol(e.to_string()))
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn nvml_try_maps_codes() {
+        assert!(nvml_try(nvmlReturn_enum_NVML_SUCCESS).is_ok());
+        assert!(matches!(
+            nvml_try(nvmlReturn_enum_NVML_ERROR_NOT_SUPPORTED),
+            Err(NvmlError::NotSupported)
+        ));
+        assert!(matches!(
+            nvml_try(nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE),
+            Err(NvmlError::InsufficientSize(None))
+        ));
+        assert!(matches!(
+            nvml_try(nvmlReturn_enum_NVML_ERROR_UNKNOWN),
+            Err(NvmlError::Unknown)
+        ));
+    }
+
+    #[test]
+    fn nvml_try_unexpected_variant() {
+        assert!(matches!(
+            nvml_try(12345),
+            Err(NvmlError::UnexpectedVariant(12345))
+        ));
+    }
+
+    #[test]
+    fn error_with_source_chains() {
+        let error: NvmlErrorWithSource = NvmlError::GpuLost.into();
+        assert!(error.source.is_none());
+        assert!(Error::source(&error).is_none());
+
+        let error = NvmlErrorWithSource {
+            error: NvmlError::SetReleaseFailed,
+            source: Some(NvmlError::Unknown),
+        };
+        assert_eq!(error.to_string(), NvmlError::SetReleaseFailed.to_string());
+        assert_eq!(
+            Error::source(&error).map(|e| e.to_string()),
+            Some(NvmlError::Unknown.to_string())
+        );
+    }
+}
@@ -0,0 +1,210 @@
+/*!
+Read a `Device`'s immutable metadata without calling into NVML every time.
+
+Tools that poll a set of GPUs often re-read identifying information such as
+the name or UUID of each device on every tick just to label their output. That
+information can't change for as long as a `Device` handle is valid, so a
+`CachedDevice` reads each piece of it from NVML the first time it's asked for
+and hands back the stored value afterwards.
+
+The following are treated as immutable and cached:
+
+* `.name()`
+* `.uuid()`
+* `.serial()`
+* `.board_part_number()`
+* `.pci_info()`
+
+Errors are never cached; a read that fails is retried the next time it's asked
+for. Everything else should be queried through `.device()` as usual.
+
+```no_run
+# use nvml_wrapper::Nvml;
+# use nvml_wrapper::error::NvmlError;
+# use std::{thread, time::Duration};
+# fn main() -> Result<(), NvmlError> {
+let nvml = Nvml::init()?;
+let device = nvml.device_by_index(0)?;
+let cached = device.cached();
+
+loop {
+    // Only the first iteration calls into NVML for the name
+    println!("{}: {}%", cached.name()?, cached.device().utilization_rates()?.gpu);
+
+    thread::sleep(Duration::from_secs(1));
+}
+# }
+```
+*/
+
+use crate::error::NvmlError;
+use crate::struct_wrappers::device::PciInfo;
+use crate::Device;
+
+use std::cell::RefCell;
+
+/**
+A `Device` that caches its immutable metadata.
+
+Obtain one via `Device.cached()`. See the module-level docs for which fields are
+cached.
+
+This is not `Sync`; give each thread its own `CachedDevice` if you need to read
+from several threads.
+*/
+#[derive(Debug)]
+pub struct CachedDevice<'device, 'nvml: 'device> {
+    device: &'device Device<'nvml>,
+    name: RefCell<Option<String>>,
+    uuid: RefCell<Option<String>>,
+    serial: RefCell<Option<String>>,
+    board_part_number: RefCell<Option<String>>,
+    pci_info: RefCell<Option<PciInfo>>,
+}
+
+impl<'device, 'nvml: 'device> CachedDevice<'device, 'nvml> {
+    /// Creates a cache for the given `Device` that has not read anything yet.
+    pub fn new(device: &'device Device<'nvml>) -> Self {
+        Self {
+            device,
+            name: RefCell::new(None),
+            uuid: RefCell::new(None),
+            serial: RefCell::new(None),
+            board_part_number: RefCell::new(None),
+            pci_info: RefCell::new(None),
+        }
+    }
+
+    /// Access the `Device` this struct wraps, for queries that aren't cached.
+    pub fn device(&self) -> &'device Device<'nvml> {
+        self.device
+    }
+
+    /// The cached equivalent of `Device.name()`.
+    ///
+    /// See that method's docs for the errors that can be returned on the first
+    /// read.
+    pub fn name(&self) -> Result<String, NvmlError> {
+        cached(&self.name, || self.device.name())
+    }
+
+    /// The cached equivalent of `Device.uuid()`.
+    ///
+    /// See that method's docs for the errors that can be returned on the first
+    /// read.
+    pub fn uuid(&self) -> Result<String, NvmlError> {
+        cached(&self.uuid, || self.device.uuid())
+    }
+
+    /// The cached equivalent of `Device.serial()`.
+    ///
+    /// See that method's docs for the errors that can be returned on the first
+    /// read.
+    pub fn serial(&self) -> Result<String, NvmlError> {
+        cached(&self.serial, || self.device.serial())
+    }
+
+    /// The cached equivalent of `Device.board_part_number()`.
+    ///
+    /// See that method's docs for the errors that can be returned on the first
+    /// read.
+    pub fn board_part_number(&self) -> Result<String, NvmlError> {
+        cached(&self.board_part_number, || self.device.board_part_number())
+    }
+
+    /// The cached equivalent of `Device.pci_info()`.
+    ///
+    /// See that method's docs for the errors that can be returned on the first
+    /// read.
+    pub fn pci_info(&self) -> Result<PciInfo, NvmlError> {
+        cached(&self.pci_info, || self.device.pci_info())
+    }
+
+    /// Forgets every cached value so that the next read of each one calls into
+    /// NVML again.
+    pub fn clear(&mut self) {
+        *self = Self::new(self.device);
+    }
+}
+
+// Returns the value in `cell`, filling it in with `read` first if it's empty.
+fn cached<T: Clone>(
+    cell: &RefCell<Option<T>>,
+    read: impl FnOnce() -> Result<T, NvmlError>,
+) -> Result<T, NvmlError> {
+    if let Some(value) = cell.borrow().as_ref() {
+        return Ok(value.clone());
+    }
+
+    let value = read()?;
+    *cell.borrow_mut() = Some(value.clone());
+
+    Ok(value)
+}
+
+#[cfg(test)]
+mod test {
+    use super::cached;
+    use crate::error::NvmlError;
+    use crate::test_utils::*;
+
+    use std::cell::{Cell, RefCell};
+
+    #[test]
+    fn caches_success() {
+        let cell = RefCell::new(None);
+        let reads = Cell::new(0);
+        let read = || {
+            reads.set(reads.get() + 1);
+            Ok(7)
+        };
+
+        assert_eq!(cached(&cell, read).unwrap(), 7);
+        assert_eq!(cached(&cell, read).unwrap(), 7);
+        assert_eq!(reads.get(), 1);
+    }
+
+    #[test]
+    fn does_not_cache_errors() {
+        let cell = RefCell::new(None);
+
+        assert!(matches!(
+            cached(&cell, || Err::<u32, _>(NvmlError::Unknown)),
+            Err(NvmlError::Unknown)
+        ));
+        assert_eq!(cell.borrow().as_ref(), None);
+        assert_eq!(cached(&cell, || Ok(3)).unwrap(), 3);
+    }
+
+    #[test]
+    fn matches_fresh_reads() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let cached = device.cached();
+
+            for _ in 0..2 {
+                assert_eq!(cached.name()?, device.name()?);
+                assert_eq!(cached.uuid()?, device.uuid()?);
+                assert_eq!(cached.pci_info()?, device.pci_info()?);
+            }
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn matches_fresh_reads_board() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let cached = device.cached();
+
+            for _ in 0..2 {
+                assert_eq!(cached.serial()?, device.serial()?);
+                assert_eq!(cached.board_part_number()?, device.board_part_number()?);
+            }
+
+            Ok(())
+        })
+    }
+}
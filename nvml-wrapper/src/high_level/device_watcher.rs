@@ -0,0 +1,559 @@
+/*!
+Watch a `Device` for changes in state that NVML doesn't raise events for.
+
+NVML's event API (see `EventLoop`) covers things like XID errors and clock
+changes, but not changes in performance state, throttle reasons, compute mode,
+or persistence mode. A `DeviceWatcher` reads a chosen set of those fields on
+every poll, remembers what it saw, and hands back a `ChangeEvent` for each
+field whose value differs from the previous poll.
+
+Throttle reasons are diffed by a `ThrottleWatcher`, so reasons that are already
+active at the first poll are reported as a change from no reasons at all. Every
+other field only records a baseline at the first poll. Fields that the `Device`
+does not support are dropped from the watcher rather than failing every poll.
+
+```no_run
+# use nvml_wrapper::Nvml;
+# use nvml_wrapper::error::NvmlError;
+use nvml_wrapper::high_level::{DeviceWatcher, WatchedField};
+# use std::time::Duration;
+
+# fn main() -> Result<(), NvmlError> {
+let nvml = Nvml::init()?;
+let device = nvml.device_by_index(0)?;
+let mut watcher = DeviceWatcher::new(
+    [WatchedField::PerformanceState, WatchedField::ThrottleReasons],
+    Duration::from_secs(1),
+);
+
+watcher.run_forever(&device, |event, state| match event {
+    Ok(event) => println!("{:?}: {:?} -> {:?}", event.field, event.old, event.new),
+    Err(e) => {
+        eprintln!("{}", e);
+        state.interrupt();
+    }
+});
+# Ok(())
+# }
+```
+*/
+
+use crate::bitmasks::device::ThrottleReasons;
+use crate::enum_wrappers::device::{ComputeMode, PerformanceState};
+use crate::error::NvmlError;
+use crate::high_level::ThrottleWatcher;
+use crate::Device;
+
+use std::collections::HashMap;
+use std::thread;
+use std::time::Duration;
+
+/// A piece of `Device` state that a `DeviceWatcher` can watch.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum WatchedField {
+    /// Read via `Device.performance_state()`.
+    PerformanceState,
+    /// Read via `Device.current_throttle_reasons()`.
+    ThrottleReasons,
+    /// Read via `Device.compute_mode()`.
+    ComputeMode,
+    /// Read via `Device.is_in_persistent_mode()`.
+    PersistenceMode,
+}
+
+/// The value of a `WatchedField` at the time it was read.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum WatchedValue {
+    PerformanceState(PerformanceState),
+    ThrottleReasons(ThrottleReasons),
+    ComputeMode(ComputeMode),
+    /// Whether or not persistence mode is enabled.
+    PersistenceMode(bool),
+}
+
+impl WatchedField {
+    /// Reads the current value of this field from the given `Device`.
+    ///
+    /// # Errors
+    ///
+    /// See the docs for the `Device` method that the variant names.
+    pub fn read(&self, device: &Device) -> Result<WatchedValue, NvmlError> {
+        Ok(match self {
+            WatchedField::PerformanceState => {
+                WatchedValue::PerformanceState(device.performance_state()?)
+            }
+            WatchedField::ThrottleReasons => {
+                WatchedValue::ThrottleReasons(device.current_throttle_reasons()?)
+            }
+            WatchedField::ComputeMode => WatchedValue::ComputeMode(device.compute_mode()?),
+            WatchedField::PersistenceMode => {
+                WatchedValue::PersistenceMode(device.is_in_persistent_mode()?)
+            }
+        })
+    }
+}
+
+/// A change in the value of a `WatchedField` between two polls.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ChangeEvent {
+    /// The field that changed.
+    pub field: WatchedField,
+    /// The value seen at the previous poll.
+    pub old: WatchedValue,
+    /// The value seen now.
+    pub new: WatchedValue,
+}
+
+/// Passed to the callback given to `DeviceWatcher.run_forever()` so that it
+/// can stop the loop.
+#[derive(Debug)]
+pub struct DeviceWatcherState {
+    interrupted: bool,
+}
+
+impl DeviceWatcherState {
+    /// Stop the loop once the callback returns.
+    pub fn interrupt(&mut self) {
+        self.interrupted = true;
+    }
+}
+
+/// Remembers the last value seen for each of a set of `WatchedField`s of a
+/// single `Device` so that only changes are reported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceWatcher {
+    // Every field that was asked for, so that `reset()` can restore fields
+    // that were dropped as unsupported
+    requested: Vec<WatchedField>,
+    fields: Vec<WatchedField>,
+    interval: Duration,
+    last: HashMap<WatchedField, WatchedValue>,
+    throttle: ThrottleWatcher,
+}
+
+impl DeviceWatcher {
+    /**
+    Creates a watcher for the given fields that has not read anything yet.
+
+    `interval` is how long `run_forever()` sleeps between polls. Fields that
+    are given more than once are only watched once.
+    */
+    pub fn new<I>(fields: I, interval: Duration) -> Self
+    where
+        I: IntoIterator<Item = WatchedField>,
+    {
+        let mut deduped = Vec::new();
+
+        for field in fields {
+            if !deduped.contains(&field) {
+                deduped.push(field);
+            }
+        }
+
+        Self {
+            requested: deduped.clone(),
+            fields: deduped,
+            interval,
+            last: HashMap::new(),
+            throttle: ThrottleWatcher::new(),
+        }
+    }
+
+    /// The fields being watched, in the order their changes are reported.
+    ///
+    /// Fields that turned out to be unsupported by the polled `Device` are no
+    /// longer listed until `.reset()` is called.
+    pub fn fields(&self) -> &[WatchedField] {
+        &self.fields
+    }
+
+    /// How long `run_forever()` sleeps between polls.
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// The value of the given field seen at the most recent poll, if any.
+    pub fn last(&self, field: WatchedField) -> Option<WatchedValue> {
+        match field {
+            WatchedField::ThrottleReasons => {
+                self.throttle.last().map(WatchedValue::ThrottleReasons)
+            }
+            _ => self.last.get(&field).cloned(),
+        }
+    }
+
+    /**
+    Reads every watched field from the given `Device` and returns a
+    `ChangeEvent` for each one that changed since the previous poll.
+
+    At the first poll, throttle reasons that are already active are reported
+    as a change from `ThrottleReasons::empty()`, the same as
+    `ThrottleWatcher.poll()`; every other field only records a baseline.
+
+    A field that the `Device` returns `NotSupported` for is dropped from the
+    watcher and not read again until `.reset()` is called. If reading any
+    field fails with another error, nothing is recorded for this poll.
+
+    Always poll the same `Device` with a given `DeviceWatcher`.
+
+    # Errors
+
+    See the docs for the `Device` methods listed on `WatchedField`'s
+    variants. `NotSupported` is never returned.
+    */
+    pub fn poll(&mut self, device: &Device) -> Result<Vec<ChangeEvent>, NvmlError> {
+        let readings = self
+            .fields
+            .iter()
+            .map(|field| (*field, field.read(device)))
+            .collect();
+
+        self.record(readings)
+    }
+
+    /**
+    Polls the given `Device` every `interval`, handing each change to the
+    given callback, until the loop is interrupted via the `DeviceWatcherState`.
+
+    # Errors
+
+    The function itself does not return anything. Errors from polling are
+    handed to the callback wrapped in a `Result`; see `.poll()` for which
+    errors can occur.
+    */
+    pub fn run_forever<F>(&mut self, device: &Device, mut callback: F)
+    where
+        F: FnMut(Result<ChangeEvent, NvmlError>, &mut DeviceWatcherState),
+    {
+        let mut state = DeviceWatcherState { interrupted: false };
+
+        loop {
+            match self.poll(device) {
+                Ok(events) => {
+                    for event in events {
+                        callback(Ok(event), &mut state);
+                    }
+                }
+                Err(e) => callback(Err(e), &mut state),
+            }
+
+            if state.interrupted {
+                break;
+            }
+
+            thread::sleep(self.interval);
+        }
+    }
+
+    /// Forgets every value seen so far, so that the next poll records a new
+    /// baseline, and watches any fields that were dropped as unsupported again.
+    pub fn reset(&mut self) {
+        self.fields = self.requested.clone();
+        self.last.clear();
+        self.throttle = ThrottleWatcher::new();
+    }
+
+    // Drops the fields that came back `NotSupported` and records the rest,
+    // unless any other error occurred.
+    fn record(
+        &mut self,
+        readings: Vec<(WatchedField, Result<WatchedValue, NvmlError>)>,
+    ) -> Result<Vec<ChangeEvent>, NvmlError> {
+        let mut values = Vec::with_capacity(readings.len());
+        let mut unsupported = Vec::new();
+
+        for (field, reading) in readings {
+            match reading {
+                Ok(value) => values.push((field, value)),
+                Err(NvmlError::NotSupported) => unsupported.push(field),
+                Err(e) => return Err(e),
+            }
+        }
+
+        self.fields.retain(|field| !unsupported.contains(field));
+        Ok(self.update(values))
+    }
+
+    // Records the given readings as the latest values and diffs them against
+    // the previous ones.
+    fn update(&mut self, readings: Vec<(WatchedField, WatchedValue)>) -> Vec<ChangeEvent> {
+        let mut events = Vec::new();
+
+        for (field, new) in readings {
+            if let WatchedValue::ThrottleReasons(reasons) = new {
+                if let Some(transition) = self.throttle.update(reasons) {
+                    events.push(ChangeEvent {
+                        field,
+                        old: WatchedValue::ThrottleReasons(transition.previous()),
+                        new,
+                    });
+                }
+
+                continue;
+            }
+
+            match self.last.insert(field, new.clone()) {
+                Some(old) if old != new => events.push(ChangeEvent { field, old, new }),
+                _ => (),
+            }
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ChangeEvent, DeviceWatcher, WatchedField, WatchedValue};
+    use crate::bitmasks::device::ThrottleReasons;
+    use crate::enum_wrappers::device::{ComputeMode, PerformanceState};
+    use crate::error::NvmlError;
+    use crate::high_level::ThrottleWatcher;
+    use crate::test_utils::*;
+
+    use std::time::Duration;
+
+    fn watcher() -> DeviceWatcher {
+        DeviceWatcher::new(
+            [
+                WatchedField::PerformanceState,
+                WatchedField::ThrottleReasons,
+                WatchedField::ComputeMode,
+                WatchedField::PersistenceMode,
+            ],
+            Duration::from_millis(100),
+        )
+    }
+
+    fn readings(
+        pstate: PerformanceState,
+        reasons: ThrottleReasons,
+        persistence: bool,
+    ) -> Vec<(WatchedField, WatchedValue)> {
+        vec![
+            (
+                WatchedField::PerformanceState,
+                WatchedValue::PerformanceState(pstate),
+            ),
+            (
+                WatchedField::ThrottleReasons,
+                WatchedValue::ThrottleReasons(reasons),
+            ),
+            (
+                WatchedField::ComputeMode,
+                WatchedValue::ComputeMode(ComputeMode::Default),
+            ),
+            (
+                WatchedField::PersistenceMode,
+                WatchedValue::PersistenceMode(persistence),
+            ),
+        ]
+    }
+
+    #[test]
+    fn dedups_fields() {
+        let watcher = DeviceWatcher::new(
+            [
+                WatchedField::ComputeMode,
+                WatchedField::PerformanceState,
+                WatchedField::ComputeMode,
+            ],
+            Duration::from_secs(1),
+        );
+
+        assert_eq!(
+            watcher.fields(),
+            &[WatchedField::ComputeMode, WatchedField::PerformanceState]
+        );
+    }
+
+    #[test]
+    fn first_update_is_baseline() {
+        let mut watcher = watcher();
+
+        let events = watcher.update(readings(
+            PerformanceState::Zero,
+            ThrottleReasons::empty(),
+            true,
+        ));
+
+        assert!(events.is_empty());
+        assert_eq!(
+            watcher.last(WatchedField::PersistenceMode),
+            Some(WatchedValue::PersistenceMode(true))
+        );
+    }
+
+    #[test]
+    fn first_update_reports_active_throttle_reasons() {
+        let mut watcher = watcher();
+
+        let events = watcher.update(readings(
+            PerformanceState::Zero,
+            ThrottleReasons::GPU_IDLE,
+            true,
+        ));
+
+        assert_eq!(
+            events,
+            vec![ChangeEvent {
+                field: WatchedField::ThrottleReasons,
+                old: WatchedValue::ThrottleReasons(ThrottleReasons::empty()),
+                new: WatchedValue::ThrottleReasons(ThrottleReasons::GPU_IDLE),
+            }]
+        );
+    }
+
+    #[test]
+    fn throttle_changes_match_throttle_watcher() {
+        let mut watcher = watcher();
+        let mut throttle = ThrottleWatcher::new();
+
+        for reasons in [
+            ThrottleReasons::GPU_IDLE,
+            ThrottleReasons::GPU_IDLE | ThrottleReasons::SW_POWER_CAP,
+            ThrottleReasons::SW_POWER_CAP,
+            ThrottleReasons::SW_POWER_CAP,
+        ] {
+            let events = watcher.update(readings(PerformanceState::Zero, reasons, true));
+            let expected = throttle.update(reasons).map(|transition| ChangeEvent {
+                field: WatchedField::ThrottleReasons,
+                old: WatchedValue::ThrottleReasons(transition.previous()),
+                new: WatchedValue::ThrottleReasons(transition.current),
+            });
+
+            assert_eq!(events.into_iter().next(), expected);
+        }
+    }
+
+    #[test]
+    fn drops_unsupported_fields() {
+        let mut watcher = watcher();
+
+        let events = watcher
+            .record(vec![
+                (
+                    WatchedField::PerformanceState,
+                    Ok(WatchedValue::PerformanceState(PerformanceState::Zero)),
+                ),
+                (WatchedField::ThrottleReasons, Err(NvmlError::NotSupported)),
+                (
+                    WatchedField::ComputeMode,
+                    Ok(WatchedValue::ComputeMode(ComputeMode::Default)),
+                ),
+                (WatchedField::PersistenceMode, Err(NvmlError::NotSupported)),
+            ])
+            .expect("unsupported fields should not fail the poll");
+
+        assert!(events.is_empty());
+        assert_eq!(
+            watcher.fields(),
+            &[WatchedField::PerformanceState, WatchedField::ComputeMode]
+        );
+        assert_eq!(
+            watcher.last(WatchedField::ComputeMode),
+            Some(WatchedValue::ComputeMode(ComputeMode::Default))
+        );
+
+        watcher.reset();
+        assert_eq!(watcher.fields(), self::watcher().fields());
+    }
+
+    #[test]
+    fn other_errors_record_nothing() {
+        let mut watcher = watcher();
+
+        let result = watcher.record(vec![
+            (
+                WatchedField::PerformanceState,
+                Ok(WatchedValue::PerformanceState(PerformanceState::Zero)),
+            ),
+            (WatchedField::ComputeMode, Err(NvmlError::GpuLost)),
+            (WatchedField::PersistenceMode, Err(NvmlError::NotSupported)),
+        ]);
+
+        assert!(matches!(result, Err(NvmlError::GpuLost)));
+        assert_eq!(watcher.fields().len(), 4);
+        assert_eq!(watcher.last(WatchedField::PerformanceState), None);
+    }
+
+    #[test]
+    fn reports_changes() {
+        let mut watcher = watcher();
+        watcher.update(readings(
+            PerformanceState::Zero,
+            ThrottleReasons::GPU_IDLE,
+            true,
+        ));
+
+        let events = watcher.update(readings(
+            PerformanceState::Eight,
+            ThrottleReasons::GPU_IDLE,
+            false,
+        ));
+
+        assert_eq!(
+            events,
+            vec![
+                ChangeEvent {
+                    field: WatchedField::PerformanceState,
+                    old: WatchedValue::PerformanceState(PerformanceState::Zero),
+                    new: WatchedValue::PerformanceState(PerformanceState::Eight),
+                },
+                ChangeEvent {
+                    field: WatchedField::PersistenceMode,
+                    old: WatchedValue::PersistenceMode(true),
+                    new: WatchedValue::PersistenceMode(false),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unchanged_readings() {
+        let mut watcher = watcher();
+        let same = || readings(PerformanceState::Two, ThrottleReasons::empty(), false);
+
+        watcher.update(same());
+        assert!(watcher.update(same()).is_empty());
+        assert!(watcher.update(same()).is_empty());
+    }
+
+    #[test]
+    fn reset_records_new_baseline() {
+        let mut watcher = watcher();
+        watcher.update(readings(
+            PerformanceState::Zero,
+            ThrottleReasons::empty(),
+            false,
+        ));
+
+        watcher.reset();
+        assert_eq!(watcher.last(WatchedField::PerformanceState), None);
+        assert_eq!(watcher.last(WatchedField::ThrottleReasons), None);
+
+        let events = watcher.update(readings(
+            PerformanceState::Eight,
+            ThrottleReasons::empty(),
+            false,
+        ));
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn poll() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let mut watcher = DeviceWatcher::new(
+                [WatchedField::PerformanceState, WatchedField::ComputeMode],
+                Duration::from_secs(1),
+            );
+
+            assert!(watcher.poll(device)?.is_empty());
+            watcher.poll(device)?;
+
+            assert!(watcher.last(WatchedField::ComputeMode).is_some());
+            Ok(())
+        })
+    }
+}
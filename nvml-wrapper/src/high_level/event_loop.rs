@@ -72,21 +72,25 @@ use crate::Nvml;
 #[cfg(feature = "serde")]
 use serde_derive::{Deserialize, Serialize};
 
-// TODO: Tests
-
 /**
 Represents the event types that an `EventLoop` can gather for you.
 
 These are analagous to the constants in `bitmasks::event`.
 
 Checking to see if the `Device` within an `Event` is the same physical device as
-another `Device` that you have on hand can be accomplished via `Device.uuid()`.
+another `Device` that you have on hand can be accomplished by comparing them with
+`==` (or via `Device.uuid()`).
 */
 #[derive(Debug)]
 pub enum Event<'nvml> {
     ClockChange(Device<'nvml>),
     CriticalXidError(Device<'nvml>, XidError),
     DoubleBitEccError(Device<'nvml>),
+    /// A MIG (Multi-Instance GPU) configuration change, such as a GPU or
+    /// compute instance being created or destroyed.
+    MigConfigChange(Device<'nvml>),
+    /// The power source changed (battery vs. AC power).
+    PowerSourceChange(Device<'nvml>),
     PowerStateChange(Device<'nvml>),
     SingleBitEccError(Device<'nvml>),
     /// Returned if none of the other `Events` are contained in the `EventData`
@@ -114,6 +118,10 @@ impl<'nvml> From<EventData<'nvml>> for Event<'nvml> {
             .contains(EventTypes::SINGLE_BIT_ECC_ERROR)
         {
             Event::SingleBitEccError(struct_.device)
+        } else if struct_.event_type.contains(EventTypes::POWER_SOURCE_CHANGE) {
+            Event::PowerSourceChange(struct_.device)
+        } else if struct_.event_type.contains(EventTypes::MIG_CONFIG_CHANGE) {
+            Event::MigConfigChange(struct_.device)
         } else {
             Event::Unknown
         }
@@ -285,3 +293,29 @@ impl EventLoopProvider for Nvml {
         Ok(EventLoop { set })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::bitmasks::event::EventTypes;
+    use crate::error::NvmlError;
+    use crate::test_utils::*;
+
+    #[test]
+    fn register_power_source_and_mig_events() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let set = nvml.create_event_set()?;
+
+            match device.register_events(
+                EventTypes::POWER_SOURCE_CHANGE | EventTypes::MIG_CONFIG_CHANGE,
+                set,
+            ) {
+                Ok(_) => Ok(()),
+                Err(e) => match e.error {
+                    NvmlError::NotSupported => Ok(()),
+                    other => Err(other),
+                },
+            }
+        })
+    }
+}
@@ -1,4 +1,19 @@
+pub mod cached_device;
+pub mod device_watcher;
 #[cfg(target_os = "linux")]
 pub mod event_loop;
+pub mod nvml_guard;
+pub mod sample_cursor;
+pub mod throttle_watcher;
+pub mod utilization_history;
+
+pub use self::cached_device::CachedDevice;
+pub use self::device_watcher::{
+    ChangeEvent, DeviceWatcher, DeviceWatcherState, WatchedField, WatchedValue,
+};
 #[cfg(target_os = "linux")]
 pub use self::event_loop::{Event, EventLoop, EventLoopProvider};
+pub use self::nvml_guard::NvmlGuard;
+pub use self::sample_cursor::SampleCursor;
+pub use self::throttle_watcher::{ThrottleTransition, ThrottleWatcher};
+pub use self::utilization_history::UtilizationHistory;
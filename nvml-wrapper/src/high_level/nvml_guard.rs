@@ -0,0 +1,98 @@
+/*!
+Shut NVML down at the end of a scope without giving up on its errors.
+
+Dropping an `Nvml` calls `nvmlShutdown()` but has nowhere to put an error if
+that call fails; `Nvml.shutdown()` surfaces the error but has to be remembered
+on every path out of a scope. An `NvmlGuard` wraps an `Nvml` and offers both:
+call `.finish()` on the happy path to shut down and get the result back, and
+if an early return or a panic skips that call, the wrapped `Nvml` is dropped
+(and shut down, silently) as usual.
+
+```no_run
+# use nvml_wrapper::Nvml;
+# use nvml_wrapper::error::NvmlError;
+use nvml_wrapper::high_level::NvmlGuard;
+
+# fn main() -> Result<(), NvmlError> {
+let nvml = NvmlGuard::new(Nvml::init()?);
+
+// `NvmlGuard` derefs to `Nvml`
+println!("{} devices", nvml.device_count()?);
+
+nvml.finish()?;
+# Ok(())
+# }
+```
+*/
+
+use crate::error::NvmlError;
+use crate::Nvml;
+
+use std::ops::Deref;
+
+/**
+Wraps an `Nvml` so that it can be shut down with error handling via
+`.finish()`, falling back to `Nvml`'s `Drop` implementation otherwise.
+
+See the module-level docs for more.
+*/
+#[derive(Debug)]
+pub struct NvmlGuard {
+    nvml: Nvml,
+}
+
+impl NvmlGuard {
+    /// Takes ownership of the given `Nvml`.
+    pub fn new(nvml: Nvml) -> Self {
+        Self { nvml }
+    }
+
+    /**
+    Shuts down the wrapped `Nvml`, returning any error that occurs.
+
+    # Errors
+
+    See `Nvml.shutdown()`.
+    */
+    pub fn finish(self) -> Result<(), NvmlError> {
+        self.nvml.shutdown()
+    }
+}
+
+impl Deref for NvmlGuard {
+    type Target = Nvml;
+
+    fn deref(&self) -> &Nvml {
+        &self.nvml
+    }
+}
+
+impl From<Nvml> for NvmlGuard {
+    fn from(nvml: Nvml) -> Self {
+        Self::new(nvml)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::NvmlGuard;
+    use crate::test_utils::*;
+
+    #[test]
+    fn finish() {
+        test(3, || {
+            let guard = NvmlGuard::new(nvml());
+            guard.device_count()?;
+
+            guard.finish()
+        })
+    }
+
+    #[test]
+    fn drop_without_finish() {
+        test(3, || {
+            let guard = NvmlGuard::from(nvml());
+            guard.device_count()
+        })
+    }
+}
@@ -0,0 +1,196 @@
+/*!
+Pull only new samples from `Device.samples()`.
+
+`Device.samples()` returns every sample the driver has buffered that is newer
+than the given timestamp. It's easy to forget to pass along the newest
+timestamp from the previous call (or to pass along the timestamp for the wrong
+sample type) and end up processing the same samples more than once. A
+`SampleCursor` remembers the newest timestamp it has seen for each `Sampling`
+type so that each `pull()` only hands back samples that haven't been seen yet.
+
+```no_run
+# use nvml_wrapper::Nvml;
+# use nvml_wrapper::error::NvmlError;
+use nvml_wrapper::enum_wrappers::device::Sampling;
+use nvml_wrapper::high_level::SampleCursor;
+# use std::{thread, time::Duration};
+
+# fn main() -> Result<(), NvmlError> {
+let nvml = Nvml::init()?;
+let device = nvml.device_by_index(0)?;
+let mut cursor = SampleCursor::new();
+
+loop {
+    for sample in cursor.pull(&device, Sampling::Power)? {
+        // ...
+    }
+
+    thread::sleep(Duration::from_secs(1));
+}
+# }
+```
+*/
+
+use crate::enum_wrappers::device::Sampling;
+use crate::error::NvmlError;
+use crate::struct_wrappers::device::Sample;
+use crate::Device;
+
+use std::collections::HashMap;
+
+/// Remembers the newest sample timestamp seen for each `Sampling` type of a
+/// single `Device`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SampleCursor {
+    last_timestamps: HashMap<Sampling, u64>,
+}
+
+impl SampleCursor {
+    /// Creates a cursor that has not seen any samples yet.
+    ///
+    /// The first `pull()` for each `Sampling` type returns every sample the
+    /// driver has buffered.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+    Gets the samples of the given type that are newer than the newest one this
+    cursor has seen, advancing the cursor past them.
+
+    Returns an empty `Vec` if there are no new samples.
+
+    Always pull from the same `Device` with a given `SampleCursor`; the
+    timestamps of samples from different devices are not comparable.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid
+    * `NotSupported`, if the `Device` does not support this feature
+    * `GpuLost`, if the `Device` has fallen off the bus or is otherwise inaccessible
+    * `UnexpectedVariant`, check that error's docs for more info
+    * `Unknown`, on any unexpected error
+    */
+    pub fn pull(
+        &mut self,
+        device: &Device,
+        sample_type: Sampling,
+    ) -> Result<Vec<Sample>, NvmlError> {
+        let last_timestamp = self.last_timestamp(&sample_type);
+
+        match device.samples(sample_type.clone(), last_timestamp) {
+            Ok(samples) => Ok(self.advance(sample_type, samples)),
+            // No samples have been taken since the last pull
+            Err(NvmlError::NotFound) => Ok(vec![]),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The CPU timestamp (in μs) of the newest sample of the given type seen so
+    /// far, which is what the next `pull()` for that type will query from.
+    pub fn last_timestamp(&self, sample_type: &Sampling) -> Option<u64> {
+        self.last_timestamps.get(sample_type).copied()
+    }
+
+    /// Forgets the newest timestamp seen for every sample type, so that the
+    /// next `pull()` for each type returns every buffered sample again.
+    pub fn reset(&mut self) {
+        self.last_timestamps.clear();
+    }
+
+    // Drops the samples that were already seen and moves the cursor for
+    // `sample_type` up to the newest of the rest.
+    pub(crate) fn advance(&mut self, sample_type: Sampling, samples: Vec<Sample>) -> Vec<Sample> {
+        let last_timestamp = self.last_timestamp(&sample_type);
+
+        let samples: Vec<Sample> = samples
+            .into_iter()
+            .filter(|s| last_timestamp.map_or(true, |t| s.timestamp > t))
+            .collect();
+
+        if let Some(newest) = samples.iter().map(|s| s.timestamp).max() {
+            self.last_timestamps.insert(sample_type, newest);
+        }
+
+        samples
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SampleCursor;
+    use crate::enum_wrappers::device::Sampling;
+    use crate::enums::device::SampleValue;
+    use crate::struct_wrappers::device::Sample;
+    use crate::test_utils::*;
+
+    fn sample(timestamp: u64) -> Sample {
+        Sample {
+            timestamp,
+            value: SampleValue::U32(timestamp as u32),
+        }
+    }
+
+    fn timestamps(samples: &[Sample]) -> Vec<u64> {
+        samples.iter().map(|s| s.timestamp).collect()
+    }
+
+    #[test]
+    fn advances() {
+        let mut cursor = SampleCursor::new();
+        assert_eq!(cursor.last_timestamp(&Sampling::Power), None);
+
+        let new = cursor.advance(Sampling::Power, vec![sample(10), sample(30), sample(20)]);
+        assert_eq!(timestamps(&new), vec![10, 30, 20]);
+        assert_eq!(cursor.last_timestamp(&Sampling::Power), Some(30));
+
+        let new = cursor.advance(Sampling::Power, vec![sample(20), sample(30), sample(40)]);
+        assert_eq!(timestamps(&new), vec![40]);
+        assert_eq!(cursor.last_timestamp(&Sampling::Power), Some(40));
+    }
+
+    #[test]
+    fn empty_without_new_data() {
+        let mut cursor = SampleCursor::new();
+        cursor.advance(Sampling::Power, vec![sample(10), sample(20)]);
+
+        assert!(cursor.advance(Sampling::Power, vec![]).is_empty());
+        assert!(cursor
+            .advance(Sampling::Power, vec![sample(10), sample(20)])
+            .is_empty());
+        assert_eq!(cursor.last_timestamp(&Sampling::Power), Some(20));
+    }
+
+    #[test]
+    fn tracks_types_separately() {
+        let mut cursor = SampleCursor::new();
+        cursor.advance(Sampling::Power, vec![sample(50)]);
+
+        let new = cursor.advance(Sampling::GpuUtilization, vec![sample(10)]);
+        assert_eq!(timestamps(&new), vec![10]);
+        assert_eq!(cursor.last_timestamp(&Sampling::Power), Some(50));
+        assert_eq!(cursor.last_timestamp(&Sampling::GpuUtilization), Some(10));
+
+        cursor.reset();
+        assert_eq!(cursor.last_timestamp(&Sampling::Power), None);
+    }
+
+    #[test]
+    fn pull() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let mut cursor = SampleCursor::new();
+            cursor.pull(device, Sampling::GpuUtilization)?;
+
+            let last_timestamp = cursor.last_timestamp(&Sampling::GpuUtilization);
+            let new = cursor.pull(device, Sampling::GpuUtilization)?;
+
+            if let Some(t) = last_timestamp {
+                assert!(new.iter().all(|s| s.timestamp > t));
+            }
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,204 @@
+/*!
+Watch for changes in why a `Device`'s clocks are being held back.
+
+`Device.current_throttle_reasons()` reports the set of reasons that are active
+right now. Logging every reading is noisy; what's usually interesting is when
+a reason kicks in or goes away. A `ThrottleWatcher` remembers the last set of
+reasons it saw and hands back a `ThrottleTransition` from `poll()` only when
+that set changes.
+
+```no_run
+# use nvml_wrapper::Nvml;
+# use nvml_wrapper::error::NvmlError;
+use nvml_wrapper::high_level::ThrottleWatcher;
+# use std::{thread, time::Duration};
+
+# fn main() -> Result<(), NvmlError> {
+let nvml = Nvml::init()?;
+let device = nvml.device_by_index(0)?;
+let mut watcher = ThrottleWatcher::new();
+
+loop {
+    if let Some(transition) = watcher.poll(&device)? {
+        println!("throttling started: {:?}", transition.added);
+        println!("throttling stopped: {:?}", transition.removed);
+    }
+
+    thread::sleep(Duration::from_secs(1));
+}
+# }
+```
+*/
+
+use crate::bitmasks::device::ThrottleReasons;
+use crate::error::NvmlError;
+use crate::Device;
+
+/// A change in the set of active throttle reasons, as returned from
+/// `ThrottleWatcher.poll()`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct ThrottleTransition {
+    /// The reasons that were not active at the previous poll but are now.
+    pub added: ThrottleReasons,
+    /// The reasons that were active at the previous poll but no longer are.
+    pub removed: ThrottleReasons,
+    /// Every reason that is active now.
+    pub current: ThrottleReasons,
+}
+
+impl ThrottleTransition {
+    /// The reasons that were active at the previous poll.
+    pub fn previous(&self) -> ThrottleReasons {
+        (self.current - self.added) | self.removed
+    }
+}
+
+/// Remembers the throttle reasons last seen for a single `Device` so that only
+/// changes are reported.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThrottleWatcher {
+    last: Option<ThrottleReasons>,
+}
+
+impl ThrottleWatcher {
+    /// Creates a watcher that has not seen any throttle reasons yet.
+    ///
+    /// The first `poll()` reports every active reason as added.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+    Reads the current throttle reasons from the given `Device` and returns how
+    they changed since the previous poll, or `None` if they didn't.
+
+    Always poll the same `Device` with a given `ThrottleWatcher`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid
+    * `NotSupported`, if the `Device` does not support this feature
+    * `GpuLost`, if the `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    pub fn poll(&mut self, device: &Device) -> Result<Option<ThrottleTransition>, NvmlError> {
+        let current = device.current_throttle_reasons()?;
+
+        Ok(self.update(current))
+    }
+
+    /// The throttle reasons seen at the most recent poll, if any.
+    pub fn last(&self) -> Option<ThrottleReasons> {
+        self.last
+    }
+
+    // Records `current` as the latest set of reasons and diffs it against the
+    // previous set.
+    pub(crate) fn update(&mut self, current: ThrottleReasons) -> Option<ThrottleTransition> {
+        let previous = self
+            .last
+            .replace(current)
+            .unwrap_or(ThrottleReasons::empty());
+
+        if previous == current {
+            return None;
+        }
+
+        Some(ThrottleTransition {
+            added: current - previous,
+            removed: previous - current,
+            current,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ThrottleTransition, ThrottleWatcher};
+    use crate::bitmasks::device::ThrottleReasons;
+    use crate::test_utils::*;
+
+    #[test]
+    fn first_update_reports_active_reasons() {
+        let mut watcher = ThrottleWatcher::new();
+        assert_eq!(watcher.last(), None);
+
+        assert_eq!(
+            watcher.update(ThrottleReasons::GPU_IDLE),
+            Some(ThrottleTransition {
+                added: ThrottleReasons::GPU_IDLE,
+                removed: ThrottleReasons::empty(),
+                current: ThrottleReasons::GPU_IDLE,
+            })
+        );
+        assert_eq!(watcher.last(), Some(ThrottleReasons::GPU_IDLE));
+    }
+
+    #[test]
+    fn first_update_without_reasons() {
+        let mut watcher = ThrottleWatcher::new();
+
+        assert_eq!(watcher.update(ThrottleReasons::empty()), None);
+        assert_eq!(watcher.last(), Some(ThrottleReasons::empty()));
+    }
+
+    #[test]
+    fn diffs_reason_sets() {
+        let mut watcher = ThrottleWatcher::new();
+        watcher.update(ThrottleReasons::GPU_IDLE | ThrottleReasons::SW_POWER_CAP);
+
+        assert_eq!(
+            watcher.update(ThrottleReasons::SW_POWER_CAP | ThrottleReasons::HW_SLOWDOWN),
+            Some(ThrottleTransition {
+                added: ThrottleReasons::HW_SLOWDOWN,
+                removed: ThrottleReasons::GPU_IDLE,
+                current: ThrottleReasons::SW_POWER_CAP | ThrottleReasons::HW_SLOWDOWN,
+            })
+        );
+
+        assert_eq!(
+            watcher.update(ThrottleReasons::empty()),
+            Some(ThrottleTransition {
+                added: ThrottleReasons::empty(),
+                removed: ThrottleReasons::SW_POWER_CAP | ThrottleReasons::HW_SLOWDOWN,
+                current: ThrottleReasons::empty(),
+            })
+        );
+    }
+
+    #[test]
+    fn previous_reasons() {
+        let transition = ThrottleTransition {
+            added: ThrottleReasons::HW_SLOWDOWN,
+            removed: ThrottleReasons::GPU_IDLE,
+            current: ThrottleReasons::SW_POWER_CAP | ThrottleReasons::HW_SLOWDOWN,
+        };
+
+        assert_eq!(
+            transition.previous(),
+            ThrottleReasons::GPU_IDLE | ThrottleReasons::SW_POWER_CAP
+        );
+    }
+
+    #[test]
+    fn unchanged_reasons() {
+        let mut watcher = ThrottleWatcher::new();
+        watcher.update(ThrottleReasons::SW_THERMAL_SLOWDOWN);
+
+        assert_eq!(watcher.update(ThrottleReasons::SW_THERMAL_SLOWDOWN), None);
+        assert_eq!(watcher.update(ThrottleReasons::SW_THERMAL_SLOWDOWN), None);
+    }
+
+    #[test]
+    fn poll() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let mut watcher = ThrottleWatcher::new();
+            watcher.poll(device)?;
+
+            assert!(watcher.last().is_some());
+            Ok(())
+        })
+    }
+}
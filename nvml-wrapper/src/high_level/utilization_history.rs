@@ -0,0 +1,236 @@
+/*!
+A bounded history of GPU utilization samples.
+
+`Device.samples()` can hand back every sample that the driver has buffered
+since a given timestamp, which is a cheap way to get high-frequency data. A
+`UtilizationHistory` keeps track of the timestamp of the newest sample it has
+seen so that each call to `poll()` only pulls in new samples, and it keeps at
+most `capacity` samples around, dropping the oldest ones first.
+
+```no_run
+# use nvml_wrapper::Nvml;
+# use nvml_wrapper::error::NvmlError;
+use nvml_wrapper::high_level::UtilizationHistory;
+# use std::{thread, time::Duration};
+
+# fn main() -> Result<(), NvmlError> {
+let nvml = Nvml::init()?;
+let device = nvml.device_by_index(0)?;
+let mut history = UtilizationHistory::new(1000);
+
+loop {
+    history.poll(&device)?;
+
+    for sample in history.samples() {
+        // ...
+    }
+
+    thread::sleep(Duration::from_secs(1));
+}
+# }
+```
+*/
+
+use crate::enum_wrappers::device::Sampling;
+use crate::error::NvmlError;
+use crate::high_level::SampleCursor;
+use crate::struct_wrappers::device::Sample;
+use crate::Device;
+
+use std::collections::{vec_deque, VecDeque};
+
+/// A ring buffer of `Sampling::GpuUtilization` samples for a single `Device`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UtilizationHistory {
+    capacity: usize,
+    samples: VecDeque<Sample>,
+    cursor: SampleCursor,
+}
+
+impl UtilizationHistory {
+    /// Creates an empty history that will hold at most `capacity` samples.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            samples: VecDeque::with_capacity(capacity),
+            cursor: SampleCursor::new(),
+        }
+    }
+
+    /**
+    Pulls any GPU utilization samples newer than the last one seen from the
+    given `Device` and appends them to this history.
+
+    Returns the number of new samples. Samples are dropped from the front of
+    the history once it is full.
+
+    Always poll the same `Device` with a given `UtilizationHistory`; the
+    timestamps of samples from different devices are not comparable.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `Device` is invalid
+    * `NotSupported`, if the `Device` does not support utilization sampling
+    * `GpuLost`, if the `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    pub fn poll(&mut self, device: &Device) -> Result<usize, NvmlError> {
+        let samples = self.cursor.pull(device, Sampling::GpuUtilization)?;
+
+        Ok(self.append(samples))
+    }
+
+    /// Iterates over the samples in this history, oldest first.
+    pub fn samples(&self) -> vec_deque::Iter<'_, Sample> {
+        self.samples.iter()
+    }
+
+    /// The most recent sample in this history, if any.
+    pub fn latest(&self) -> Option<&Sample> {
+        self.samples.back()
+    }
+
+    /// The CPU timestamp (in μs) of the newest sample seen so far, which is
+    /// what the next `poll()` will query from.
+    pub fn last_timestamp(&self) -> Option<u64> {
+        self.cursor.last_timestamp(&Sampling::GpuUtilization)
+    }
+
+    /// The maximum number of samples this history will hold.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of samples currently in this history.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Whether or not this history has any samples in it.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Removes all samples from this history.
+    ///
+    /// The last seen timestamp is kept so that samples that were already seen
+    /// are not pulled in again.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    // Appends samples that the cursor has already filtered down to new ones,
+    // returning how many there were.
+    fn append(&mut self, samples: Vec<Sample>) -> usize {
+        let count = samples.len();
+
+        for sample in samples {
+            if self.capacity == 0 {
+                break;
+            }
+
+            if self.samples.len() == self.capacity {
+                self.samples.pop_front();
+            }
+
+            self.samples.push_back(sample);
+        }
+
+        count
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::UtilizationHistory;
+    use crate::enum_wrappers::device::Sampling;
+    use crate::enums::device::SampleValue;
+    use crate::struct_wrappers::device::Sample;
+    use crate::test_utils::*;
+
+    fn sample(timestamp: u64) -> Sample {
+        Sample {
+            timestamp,
+            value: SampleValue::U32(timestamp as u32),
+        }
+    }
+
+    fn timestamps(history: &UtilizationHistory) -> Vec<u64> {
+        history.samples().map(|s| s.timestamp).collect()
+    }
+
+    // What `poll()` does with the samples it gets back from the `Device`
+    fn push_samples(history: &mut UtilizationHistory, samples: Vec<Sample>) -> usize {
+        let samples = history.cursor.advance(Sampling::GpuUtilization, samples);
+        history.append(samples)
+    }
+
+    #[test]
+    fn bounded() {
+        let mut history = UtilizationHistory::new(3);
+
+        assert_eq!(push_samples(&mut history, (1..=5).map(sample).collect()), 5);
+        assert_eq!(history.len(), 3);
+        assert_eq!(timestamps(&history), vec![3, 4, 5]);
+
+        assert_eq!(push_samples(&mut history, vec![sample(6)]), 1);
+        assert_eq!(timestamps(&history), vec![4, 5, 6]);
+        assert_eq!(history.latest().map(|s| s.timestamp), Some(6));
+    }
+
+    #[test]
+    fn timestamp_advances() {
+        let mut history = UtilizationHistory::new(10);
+        assert_eq!(history.last_timestamp(), None);
+
+        push_samples(&mut history, vec![sample(10), sample(20)]);
+        assert_eq!(history.last_timestamp(), Some(20));
+
+        // Samples that were already seen are skipped
+        assert_eq!(
+            push_samples(&mut history, vec![sample(15), sample(20), sample(30)]),
+            1
+        );
+        assert_eq!(history.last_timestamp(), Some(30));
+        assert_eq!(timestamps(&history), vec![10, 20, 30]);
+
+        history.clear();
+        assert!(history.is_empty());
+        assert_eq!(history.last_timestamp(), Some(30));
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let mut history = UtilizationHistory::new(0);
+
+        assert_eq!(push_samples(&mut history, vec![sample(1), sample(2)]), 2);
+        assert!(history.is_empty());
+        assert_eq!(history.last_timestamp(), Some(2));
+    }
+
+    #[test]
+    fn out_of_order_samples() {
+        let mut history = UtilizationHistory::new(10);
+
+        push_samples(&mut history, vec![sample(30), sample(10)]);
+        assert_eq!(history.last_timestamp(), Some(30));
+
+        // Samples at or before the newest one seen are not pulled in again
+        assert_eq!(push_samples(&mut history, vec![sample(10), sample(20)]), 0);
+        assert_eq!(timestamps(&history), vec![30, 10]);
+    }
+
+    #[test]
+    fn poll() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let mut history = UtilizationHistory::new(100);
+            history.poll(device)?;
+            history.poll(device)?;
+
+            assert!(history.len() <= history.capacity());
+            Ok(())
+        })
+    }
+}
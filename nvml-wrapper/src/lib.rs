@@ -85,6 +85,10 @@ way to avoid bumping this.
 The `serde` feature can be toggled on in order to `#[derive(Serialize, Deserialize)]`
 for every NVML data structure.
 
+The `serde-human` feature enables `serde` and additionally serializes the enum
+wrappers as the name of their variant (e.g. `"P0"` for `PerformanceState::Zero`)
+in every data format, which makes for more readable output.
+
 [nvml]: https://developer.nvidia.com/nvidia-management-library-nvml
 [libloading]: https://github.com/nagisa/rust_libloading
 [once_cell]: https://docs.rs/once_cell/latest/once_cell/sync/struct.Lazy.html
@@ -108,13 +112,19 @@ pub mod struct_wrappers;
 pub mod structs;
 #[cfg(test)]
 mod test_utils;
+#[cfg(target_os = "linux")]
+pub mod topology;
 pub mod unit;
+pub mod vgpu;
 
 // Re-exports for convenience
 pub use crate::device::Device;
 pub use crate::event::EventSet;
 pub use crate::nv_link::NvLink;
+#[cfg(target_os = "linux")]
+pub use crate::topology::Topology;
 pub use crate::unit::Unit;
+pub use crate::vgpu::{VgpuInstance, VgpuTypeId};
 
 /// Re-exports from `nvml-wrapper-sys` that are necessary for use of this wrapper.
 pub mod sys_exports {
@@ -140,6 +150,7 @@ use static_assertions::assert_impl_all;
 #[cfg(target_os = "linux")]
 use crate::enum_wrappers::device::TopologyLevel;
 
+use crate::device::retry_with_required_size;
 use crate::error::{nvml_sym, nvml_try, NvmlError};
 use crate::ffi::bindings::*;
 
@@ -148,6 +159,7 @@ use crate::struct_wrappers::ExcludedDeviceInfo;
 #[cfg(target_os = "linux")]
 use crate::struct_wrappers::device::PciInfo;
 use crate::struct_wrappers::unit::HwbcEntry;
+use crate::struct_wrappers::vgpu::{VgpuMetadata, VgpuPgpuCompatibility, VgpuPgpuMetadata};
 
 use crate::bitmasks::InitFlags;
 
@@ -171,6 +183,53 @@ pub fn cuda_driver_version_minor(version: i32) -> i32 {
     (version % 1000) / 10
 }
 
+/**
+Converts a PCI bus ID into the canonical `domain:bus:device.function` form that
+NVML reports (e.g. `00000000:01:00.0`).
+
+Accepts IDs with or without the domain, with short or long domains, and in any
+hex case. Returns `None` if the input can't be interpreted as a PCI bus ID.
+*/
+/// Decodes `bytes` as UTF-8, dropping an incomplete multibyte character at the
+/// end (as left behind by cropping a string to a fixed length).
+fn string_from_cropped_utf8(bytes: Vec<u8>) -> Result<String, std::str::Utf8Error> {
+    match String::from_utf8(bytes) {
+        Ok(string) => Ok(string),
+        // `error_len()` is `None` if the input ended partway through a character
+        Err(e) if e.utf8_error().error_len().is_none() => {
+            let valid_up_to = e.utf8_error().valid_up_to();
+            let mut bytes = e.into_bytes();
+            bytes.truncate(valid_up_to);
+
+            String::from_utf8(bytes).map_err(|e| e.utf8_error())
+        }
+        Err(e) => Err(e.utf8_error()),
+    }
+}
+
+pub(crate) fn normalize_pci_bus_id(pci_bus_id: &str) -> Option<String> {
+    let hex = |s: &str| u32::from_str_radix(s.trim(), 16).ok();
+
+    let parts: Vec<&str> = pci_bus_id.trim().split(':').collect();
+    let (domain, bus, dev_fn) = match parts[..] {
+        [domain, bus, dev_fn] => (hex(domain)?, hex(bus)?, dev_fn),
+        [bus, dev_fn] => (0, hex(bus)?, dev_fn),
+        _ => return None,
+    };
+
+    let (device, function) = dev_fn.split_once('.')?;
+    let (device, function) = (hex(device)?, hex(function)?);
+
+    if bus > 0xff || device > 0x1f || function > 0x7 {
+        return None;
+    }
+
+    Some(format!(
+        "{:08X}:{:02X}:{:02X}.{:X}",
+        domain, bus, device, function
+    ))
+}
+
 /**
 The main struct that this library revolves around.
 
@@ -184,6 +243,7 @@ being `Send` + `Sync`. You can `.clone()` an `Arc` wrapped `NVML` and enjoy usin
 
 NOTE: If you care about possible errors returned from `nvmlShutdown()`, use the `.shutdown()`
 method on this struct. **The `Drop` implementation ignores errors.**
+`high_level::NvmlGuard` can help with that on code paths that may return early.
 
 When reading documentation on this struct and its members, remember that a lot of it,
 especially in regards to errors returned, is copied from NVIDIA's docs. While they can be found
@@ -268,6 +328,9 @@ impl Nvml {
     //
     // Also, don't attach any GPUs during initialization
     Nvml::init_with_flags(InitFlags::NO_GPUS | InitFlags::NO_ATTACH)?;
+
+    // The same combination of flags is also available as a shorthand
+    Nvml::init_with_flags(InitFlags::all_safe())?;
     # Ok(())
     # }
     ```
@@ -449,6 +512,13 @@ impl Nvml {
     // Tested
     #[doc(alias = "nvmlSystemGetProcessName")]
     pub fn sys_process_name(&self, pid: u32, length: usize) -> Result<String, NvmlError> {
+        let name = self.sys_process_name_bytes(pid, length)?;
+
+        Ok(String::from_utf8(name).map_err(|e| e.utf8_error())?)
+    }
+
+    // Helper for the above methods. Returns the name without decoding it.
+    fn sys_process_name_bytes(&self, pid: u32, length: usize) -> Result<Vec<u8>, NvmlError> {
         let sym = nvml_sym(self.lib.nvmlSystemGetProcessName.as_ref())?;
 
         unsafe {
@@ -457,10 +527,69 @@ impl Nvml {
             nvml_try(sym(pid, name_vec.as_mut_ptr(), length as c_uint))?;
 
             let name_raw = CStr::from_ptr(name_vec.as_ptr());
-            Ok(name_raw.to_str()?.into())
+            Ok(name_raw.to_bytes().to_vec())
+        }
+    }
+
+    /**
+    Gets the full name of the process for the given process ID.
+
+    Unlike `.sys_process_name()`, this does not require guessing a buffer
+    length. It starts with a 64-byte buffer and retries with a larger one
+    whenever the name NVML returns fills the buffer (indicating that it may
+    have been cropped), up to a maximum of 4096 bytes.
+
+    The name is only decoded once it fits in the buffer, so a multibyte
+    character split by a too-small buffer doesn't cause an error. If the name
+    is still cropped at 4096 bytes, a character split at the end is dropped.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NotFound`, if the process does not exist
+    * `NoPermission`, if the user doesn't have permission to perform the operation
+    * `Utf8Error`, if the string obtained from the C function is not valid UTF-8
+    * `Unknown`, on any unexpected error
+    */
+    pub fn sys_process_name_full(&self, pid: u32) -> Result<String, NvmlError> {
+        let mut length = 64;
+
+        loop {
+            let name = self.sys_process_name_bytes(pid, length)?;
+
+            // NVML leaves room for the null terminator, so a name this long
+            // means the buffer was filled and the name may have been cropped
+            if name.len() + 1 < length || length >= 4096 {
+                return Ok(string_from_cropped_utf8(name)?);
+            }
+
+            length *= 2;
         }
     }
 
+    /**
+    Gets the names of the processes for the given process IDs, each cropped to
+    the provided length.
+
+    This calls `.sys_process_name()` once per PID. Each PID's result is
+    independent of the others, so a process that has exited or that the user
+    doesn't have permission to inspect doesn't fail the whole batch. The
+    returned `Vec` has the same order as `pids`.
+
+    # Errors
+
+    See `.sys_process_name()` for the errors that may be returned for each PID.
+    */
+    pub fn process_names(
+        &self,
+        pids: &[u32],
+        length: usize,
+    ) -> Vec<(u32, Result<String, NvmlError>)> {
+        pids.iter()
+            .map(|&pid| (pid, self.sys_process_name(pid, length)))
+            .collect()
+    }
+
     /**
     Acquire the handle for a particular device based on its index (starts at 0).
 
@@ -504,6 +633,35 @@ impl Nvml {
         }
     }
 
+    /**
+    Acquire the handle for a particular device based on its index, checking the
+    index against `.device_count()` first.
+
+    Unlike `.device_by_index()`, an out-of-range index is reported as a
+    `DeviceIndexOutOfRange` error carrying the number of devices rather than
+    NVML's `InvalidArg`, and no attempt is made to get a handle for it.
+
+    # Errors
+
+    * `DeviceIndexOutOfRange`, if `index` is not less than the number of devices
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InsufficientPower`, if any attached devices have improperly attached external power cables
+    * `NoPermission`, if the user doesn't have permission to talk to this device
+    * `IrqIssue`, if the NVIDIA kernel detected an interrupt issue with the attached GPUs
+    * `GpuLost`, if the target GPU has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+    */
+    // Tested
+    pub fn device_by_index_checked(&self, index: u32) -> Result<Device<'_>, NvmlError> {
+        let count = self.device_count()?;
+
+        if index >= count {
+            return Err(NvmlError::DeviceIndexOutOfRange { index, count });
+        }
+
+        self.device_by_index(index)
+    }
+
     /**
     Acquire the handle for a particular device based on its PCI bus ID.
 
@@ -512,6 +670,10 @@ impl Nvml {
 
     The bus ID corresponds to the `bus_id` returned by `Device.pci_info()`.
 
+    The given ID is normalized to the form NVML uses before the lookup, so
+    `0000:01:00.0`, `01:00.0` and `00000000:01:00.0` all find the same device
+    regardless of hex case. IDs that can't be parsed are passed to NVML as-is.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
@@ -534,7 +696,10 @@ impl Nvml {
         let sym = nvml_sym(self.lib.nvmlDeviceGetHandleByPciBusId_v2.as_ref())?;
 
         unsafe {
-            let c_string = CString::new(pci_bus_id)?;
+            let c_string = match normalize_pci_bus_id(pci_bus_id.as_ref()) {
+                Some(normalized) => CString::new(normalized)?,
+                None => CString::new(pci_bus_id)?,
+            };
             let mut device: nvmlDevice_t = mem::zeroed();
 
             nvml_try(sym(c_string.as_ptr(), &mut device))?;
@@ -564,6 +729,53 @@ impl Nvml {
         }
     }
 
+    /**
+    Acquire the handle for a particular device based on its board serial number.
+
+    This is a working replacement for the deprecated `.device_by_serial()`. It
+    goes through every `Device` in enumeration order and compares the given
+    serial against `Device.serial()`. Both GPUs on a dual GPU board share the
+    same serial; the first of them is returned. Use `.device_by_uuid()` if you
+    need to tell them apart.
+
+    `Device`s that return `NoPermission` or `GpuLost` when getting their
+    handle, or `NotSupported`, `NoPermission`, or `GpuLost` when getting their
+    serial, are skipped.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NotFound`, if `serial` does not match any device on the system
+    * `Unknown`, on any unexpected error
+
+    In addition, all of the errors other than the skipped ones returned by:
+
+    * `.device_count()`
+    * `.device_by_index()`
+    * `Device.serial()`
+    */
+    // Tested
+    pub fn device_by_serial_scan(&self, serial: &str) -> Result<Device<'_>, NvmlError> {
+        for index in 0..self.device_count()? {
+            let device = match self.device_by_index(index) {
+                Ok(device) => device,
+                Err(NvmlError::NoPermission) | Err(NvmlError::GpuLost) => continue,
+                Err(e) => return Err(e),
+            };
+
+            match device.serial() {
+                Ok(s) if s == serial => return Ok(device),
+                Ok(_) => (),
+                Err(NvmlError::NotSupported)
+                | Err(NvmlError::NoPermission)
+                | Err(NvmlError::GpuLost) => (),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Err(NvmlError::NotFound)
+    }
+
     /**
     Acquire the handle for a particular device based on its globally unique immutable
     UUID.
@@ -708,6 +920,122 @@ impl Nvml {
         }
     }
 
+    /**
+    Groups all of the `Device`s in the system by the physical board that they
+    are on.
+
+    Multi-GPU boards (such as the K10 or K80) show up as multiple `Device`s;
+    each inner `Vec` contains the `Device`s that share a board. Groups are in
+    the order that their first `Device` was enumerated in.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NotSupported`, if the same-board check is not supported by a `Device`
+    * `GpuLost`, if a `Device` has fallen off the bus or is otherwise inaccessible
+    * `Unknown`, on any unexpected error
+
+    In addition, all of the errors returned by:
+
+    * `.device_count()`
+    * `.device_by_index()`
+    * `.are_devices_on_same_board()`
+    */
+    pub fn group_devices_by_board(&self) -> Result<Vec<Vec<Device<'_>>>, NvmlError> {
+        let mut groups: Vec<Vec<Device<'_>>> = Vec::new();
+
+        for index in 0..self.device_count()? {
+            let device = self.device_by_index(index)?;
+
+            let mut group_index = None;
+            for (i, group) in groups.iter().enumerate() {
+                if self.are_devices_on_same_board(&group[0], &device)? {
+                    group_index = Some(i);
+                    break;
+                }
+            }
+
+            match group_index {
+                Some(i) => groups[i].push(device),
+                None => groups.push(vec![device]),
+            }
+        }
+
+        Ok(groups)
+    }
+
+    /**
+    Gets all of the `Device`s in the system that this process can access.
+
+    In containers that restrict access to some GPUs (e.g. via cgroups),
+    `.device_count()` still includes the GPUs that can't be used. This skips
+    every `Device` that returns `NoPermission` or `GpuLost`, either when getting
+    its handle or when reading its UUID as a probe. Devices are in enumeration
+    order.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `Unknown`, on any unexpected error
+
+    In addition, all of the errors other than `NoPermission` and `GpuLost`
+    returned by:
+
+    * `.device_count()`
+    * `.device_by_index()`
+    * `Device.uuid()`
+    */
+    pub fn accessible_devices(&self) -> Result<Vec<Device<'_>>, NvmlError> {
+        let mut devices = Vec::new();
+
+        for index in 0..self.device_count()? {
+            let device = match self.device_by_index(index) {
+                Ok(device) => device,
+                Err(NvmlError::NoPermission) | Err(NvmlError::GpuLost) => continue,
+                Err(e) => return Err(e),
+            };
+
+            match device.uuid() {
+                Ok(_) => devices.push(device),
+                Err(NvmlError::NoPermission) | Err(NvmlError::GpuLost) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(devices)
+    }
+
+    /**
+    Builds the GPU-to-GPU topology of all of the `Device`s in the system.
+
+    The common ancestor of every pair of devices is looked up once, up front;
+    see `Topology` for details.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `NotSupported`, if a `Device` or the OS does not support this feature
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, an error has occurred in the underlying topology discovery
+
+    In addition, all of the errors returned by:
+
+    * `.device_count()`
+    * `.device_by_index()`
+
+    # Platform Support
+
+    Only supports Linux.
+    */
+    #[cfg(target_os = "linux")]
+    pub fn topology(&self) -> Result<Topology<'_>, NvmlError> {
+        let devices = (0..self.device_count()?)
+            .map(|index| self.device_by_index(index))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Topology::new(devices)
+    }
+
     /**
     Gets the set of GPUs that have a CPU affinity with the given CPU number.
 
@@ -759,9 +1087,13 @@ impl Nvml {
     /**
     Gets the IDs and firmware versions for any Host Interface Cards in the system.
 
+    If the number of HICs changes while this is being called, the query is
+    retried a bounded number of times.
+
     # Errors
 
     * `Uninitialized`, if the library has not been successfully initialized
+    * `InsufficientSize`, if HICs kept being added while this was retrying
 
     # Device Support
 
@@ -773,17 +1105,26 @@ impl Nvml {
     pub fn hic_versions(&self) -> Result<Vec<HwbcEntry>, NvmlError> {
         let sym = nvml_sym(self.lib.nvmlSystemGetHicVersion.as_ref())?;
 
-        unsafe {
-            let mut count: c_uint = match self.hic_count()? {
-                0 => return Ok(vec![]),
-                value => value,
-            };
-            let mut hics: Vec<nvmlHwbcEntry_t> = vec![mem::zeroed(); count as usize];
+        let count = match self.hic_count()? {
+            0 => return Ok(vec![]),
+            value => value,
+        };
+
+        // HICs may come or go between the count query and this one
+        retry_with_required_size(count as usize, |size| unsafe {
+            let mut count = size as c_uint;
+            let mut hics: Vec<nvmlHwbcEntry_t> = vec![mem::zeroed(); size];
 
-            nvml_try(sym(&mut count, hics.as_mut_ptr()))?;
+            match sym(&mut count, hics.as_mut_ptr()) {
+                nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => {
+                    return Err(NvmlError::InsufficientSize(Some(count as usize)))
+                }
+                other => nvml_try(other)?,
+            }
 
+            hics.truncate(count as usize);
             hics.into_iter().map(HwbcEntry::try_from).collect()
-        }
+        })
     }
 
     /**
@@ -807,6 +1148,10 @@ impl Nvml {
             NVIDIA doesn't even say that `count` will be set to the count if
             `InsufficientSize` is returned. But we can assume sanity, right?
 
+            Unlike most NVML count queries, this one can't be made by passing
+            a null buffer; NVIDIA documents that as an `InvalidArg` error. So
+            we probe with a single-entry buffer instead.
+
             The idea here is:
             If there are 0 HICs, NVML_SUCCESS is returned, `count` is set
               to 0. We return count, all good.
@@ -973,6 +1318,49 @@ impl Nvml {
             ExcludedDeviceInfo::try_from(info)
         }
     }
+
+    /**
+    Checks whether a vGPU with the given metadata can run on (be migrated to) a
+    physical GPU with the given metadata.
+
+    Obtain the metadata via `VgpuInstance.metadata()` and
+    `Device.vgpu_metadata()`; they don't need to come from the same host.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if either set of metadata is invalid
+    * `NotSupported`, if the host is not running a vGPU-capable driver
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlGetVgpuCompatibility")]
+    pub fn vgpu_compatibility(
+        &self,
+        vgpu_metadata: &VgpuMetadata,
+        pgpu_metadata: &VgpuPgpuMetadata,
+    ) -> Result<VgpuPgpuCompatibility, NvmlError> {
+        let sym = nvml_sym(self.lib.nvmlGetVgpuCompatibility.as_ref())?;
+
+        // NVML takes mutable pointers but doesn't document writing to them, so
+        // hand it copies
+        let mut vgpu = vgpu_metadata.to_buffer();
+        let mut pgpu = pgpu_metadata.to_buffer();
+
+        unsafe {
+            let mut compatibility: nvmlVgpuPgpuCompatibility_t = mem::zeroed();
+
+            nvml_try(sym(
+                vgpu.as_mut_ptr(),
+                pgpu.as_mut_ptr(),
+                &mut compatibility,
+            ))?;
+            Ok(VgpuPgpuCompatibility::from(compatibility))
+        }
+    }
 }
 
 /// This `Drop` implementation ignores errors! Use the `.shutdown()` method on
@@ -1063,6 +1451,16 @@ mod test {
         Nvml::init_with_flags(InitFlags::NO_GPUS).unwrap();
     }
 
+    #[test]
+    fn init_with_combined_flags() {
+        assert_eq!(
+            InitFlags::all_safe(),
+            InitFlags::NO_GPUS | InitFlags::NO_ATTACH
+        );
+
+        Nvml::init_with_flags(InitFlags::all_safe()).unwrap();
+    }
+
     #[test]
     fn shutdown() {
         test(3, || nvml().shutdown())
@@ -1114,12 +1512,71 @@ mod test {
         })
     }
 
+    #[test]
+    fn sys_process_name_full() {
+        test(3, || {
+            let nvml = nvml();
+            // The test binary's path is usually longer than the 64-byte buffer
+            // that the method starts with
+            let pid = std::process::id();
+
+            match nvml.sys_process_name_full(pid) {
+                Ok(full) => {
+                    assert!(full.starts_with(&nvml.sys_process_name(pid, 16)?));
+                    Ok(full)
+                }
+                Err(NvmlError::NoPermission) => Ok("No permission error".into()),
+                Err(e) => Err(e),
+            }
+        })
+    }
+
+    #[test]
+    fn process_names() {
+        test(3, || {
+            let nvml = nvml();
+            // This process' PID should resolve, `u32::MAX` should not
+            let pids = [std::process::id(), u32::MAX];
+            let names = nvml.process_names(&pids, 64);
+
+            assert_eq!(names.len(), pids.len());
+            assert_eq!(names[0].0, pids[0]);
+            assert_eq!(names[1].0, pids[1]);
+            assert!(names[1].1.is_err());
+
+            match names.into_iter().next().unwrap().1 {
+                Err(NvmlError::NoPermission) => Ok("No permission error".into()),
+                v => v,
+            }
+        })
+    }
+
     #[test]
     fn device_by_index() {
         let nvml = nvml();
         test(3, || nvml.device_by_index(0))
     }
 
+    #[test]
+    fn device_by_index_checked() {
+        let nvml = nvml();
+        test(3, || nvml.device_by_index_checked(0))
+    }
+
+    #[test]
+    fn device_by_index_checked_out_of_range() {
+        let nvml = nvml();
+        let count = nvml.device_count().expect("device count");
+
+        match nvml.device_by_index_checked(count) {
+            Err(NvmlError::DeviceIndexOutOfRange { index, count: c }) => {
+                assert_eq!(index, count);
+                assert_eq!(c, count);
+            }
+            other => panic!("expected `DeviceIndexOutOfRange`, got {:?}", other),
+        }
+    }
+
     #[test]
     fn device_by_pci_bus_id() {
         let nvml = nvml();
@@ -1129,6 +1586,85 @@ mod test {
         })
     }
 
+    #[test]
+    fn device_by_pci_bus_id_variants() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let info = device.pci_info()?;
+
+            let short_domain =
+                format!("{:04x}:{:02x}:{:02x}.0", info.domain, info.bus, info.device);
+            let no_domain = format!("{:02x}:{:02x}.0", info.bus, info.device);
+
+            for id in &[info.bus_id.to_lowercase(), short_domain, no_domain] {
+                let found = nvml.device_by_pci_bus_id(id.as_str())?;
+                assert_eq!(found.pci_info()?.bus_id, info.bus_id);
+            }
+
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn normalize_pci_bus_id_formats() {
+        let expected = Some("00000000:01:00.0".to_string());
+
+        assert_eq!(normalize_pci_bus_id("00000000:01:00.0"), expected);
+        assert_eq!(normalize_pci_bus_id("0000:01:00.0"), expected);
+        assert_eq!(normalize_pci_bus_id("01:00.0"), expected);
+        assert_eq!(normalize_pci_bus_id(" 0000:01:00.0 "), expected);
+
+        assert_eq!(
+            normalize_pci_bus_id("0000:3b:1f.7"),
+            Some("00000000:3B:1F.7".to_string())
+        );
+        assert_eq!(
+            normalize_pci_bus_id("0001:3B:00.0"),
+            Some("00000001:3B:00.0".to_string())
+        );
+    }
+
+    // `Device.verify_pci_bus_id()` relies on NVML's own bus IDs surviving
+    // normalization unchanged
+    #[test]
+    fn normalize_pci_bus_id_is_idempotent() {
+        for id in [
+            "00000000:01:00.0",
+            "0000:3b:1f.7",
+            "01:00.0",
+            "0001:3B:00.1",
+        ] {
+            let normalized = normalize_pci_bus_id(id).expect("valid bus ID");
+            assert_eq!(normalize_pci_bus_id(&normalized), Some(normalized));
+        }
+    }
+
+    #[test]
+    fn normalize_pci_bus_id_rejects_garbage() {
+        assert_eq!(normalize_pci_bus_id(""), None);
+        assert_eq!(normalize_pci_bus_id("not a bus id"), None);
+        assert_eq!(normalize_pci_bus_id("0000:01:00"), None);
+        assert_eq!(normalize_pci_bus_id("0000:100:00.0"), None);
+        assert_eq!(normalize_pci_bus_id("0000:01:20.0"), None);
+        assert_eq!(normalize_pci_bus_id("0000:01:00.8"), None);
+        assert_eq!(normalize_pci_bus_id("0:0000:01:00.0"), None);
+    }
+
+    #[test]
+    fn string_from_cropped_utf8_drops_split_character() {
+        assert_eq!(
+            string_from_cropped_utf8(b"nvidia".to_vec()).unwrap(),
+            "nvidia"
+        );
+
+        // "é" is 0xC3 0xA9; cropping leaves only its first byte
+        assert_eq!(
+            string_from_cropped_utf8(b"caf\xC3".to_vec()).unwrap(),
+            "caf"
+        );
+        assert!(string_from_cropped_utf8(b"ca\xFFf".to_vec()).is_err());
+    }
+
     // Can't get serial on my machine
     #[ignore = "my machine does not support this call"]
     #[test]
@@ -1142,6 +1678,28 @@ mod test {
         })
     }
 
+    #[test]
+    fn device_by_serial_scan() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let serial = match device.serial() {
+                Ok(serial) => serial,
+                Err(NvmlError::NotSupported) => return Ok(()),
+                Err(e) => return Err(e),
+            };
+
+            let found = nvml.device_by_serial_scan(&serial)?;
+            assert_eq!(found.serial()?, serial);
+
+            assert!(matches!(
+                nvml.device_by_serial_scan("not a serial"),
+                Err(NvmlError::NotFound)
+            ));
+
+            Ok(())
+        })
+    }
+
     #[test]
     fn device_by_uuid() {
         let nvml = nvml();
@@ -1185,6 +1743,54 @@ mod test {
             .expect("bool");
     }
 
+    // I don't have 2 devices
+    #[ignore = "my machine does not support this call"]
+    #[test]
+    fn group_devices_by_board() {
+        let nvml = nvml();
+        let groups = nvml.group_devices_by_board().expect("groups");
+
+        let count: usize = groups.iter().map(|g| g.len()).sum();
+        assert_eq!(count, nvml.device_count().expect("count") as usize);
+
+        for group in groups.iter() {
+            for device in group.iter() {
+                assert!(nvml
+                    .are_devices_on_same_board(&group[0], device)
+                    .expect("bool"));
+            }
+        }
+    }
+
+    #[test]
+    fn accessible_devices() {
+        let nvml = nvml();
+        test(3, || {
+            let devices = nvml.accessible_devices()?;
+            assert!(devices.len() <= nvml.device_count()? as usize);
+
+            Ok(devices)
+        })
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn topology() {
+        let nvml = nvml();
+        let topology = nvml.topology().expect("topology");
+        let count = topology.devices().len();
+
+        assert_eq!(count, nvml.device_count().expect("count") as usize);
+
+        for i in 0..count {
+            assert_eq!(topology.level(i, i), None);
+
+            for j in (0..count).filter(|&j| j != i) {
+                assert_eq!(topology.level(i, j), topology.level(j, i));
+            }
+        }
+    }
+
     #[cfg(target_os = "linux")]
     #[test]
     fn topology_gpu_set() {
@@ -1198,6 +1804,17 @@ mod test {
         test(3, || nvml.hic_versions())
     }
 
+    #[test]
+    fn hic_versions_matches_count() {
+        let nvml = nvml();
+        test(3, || {
+            let versions = nvml.hic_versions()?;
+            assert_eq!(versions.len(), nvml.hic_count()? as usize);
+
+            Ok(versions)
+        })
+    }
+
     #[test]
     fn unit_count() {
         test(3, || nvml().unit_count())
@@ -1231,6 +1848,21 @@ mod test {
         test(3, || nvml.excluded_device_count())
     }
 
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn vgpu_compatibility() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            let pgpu_metadata = device.vgpu_metadata()?;
+
+            device
+                .active_vgpus()?
+                .iter()
+                .map(|i| nvml.vgpu_compatibility(&i.metadata()?, &pgpu_metadata))
+                .collect::<Result<Vec<_>, _>>()
+        })
+    }
+
     #[test]
     fn excluded_device_info() {
         let nvml = nvml();
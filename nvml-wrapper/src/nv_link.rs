@@ -2,7 +2,7 @@ use crate::Device;
 
 use crate::enum_wrappers::{
     bool_from_state,
-    nv_link::{Capability, ErrorCounter},
+    nv_link::{Capability, ErrorCounter, RemoteDeviceType},
     state_from_bool,
 };
 
@@ -13,6 +13,7 @@ use crate::struct_wrappers::{device::PciInfo, nv_link::UtilizationControl};
 use crate::structs::nv_link::UtilizationCounter;
 
 use std::{
+    collections::HashMap,
     convert::TryFrom,
     mem,
     os::raw::{c_uint, c_ulonglong},
@@ -172,6 +173,38 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
         }
     }
 
+    /**
+    Gets whether or not this `Device` / `NvLink` has each `Capability`.
+
+    This calls `.has_capability()` for every `Capability` variant and collects
+    the results into a map.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `link` or `Device` within this `NvLink` struct instance is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Pascal or newer fully supported devices.
+    */
+    // Test written
+    pub fn capabilities(&self) -> Result<HashMap<Capability, bool>, NvmlError> {
+        [
+            Capability::P2p,
+            Capability::SysMemAccess,
+            Capability::P2pAtomics,
+            Capability::SysMemAtomics,
+            Capability::SliBridge,
+            Capability::ValidLink,
+        ]
+        .into_iter()
+        .map(|cap| Ok((cap.clone(), self.has_capability(cap)?)))
+        .collect()
+    }
+
     /**
     Gets the PCI information for this `NvLink`'s remote node.
 
@@ -207,6 +240,41 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
         }
     }
 
+    /**
+    Gets the type of device on the other end of this `NvLink`.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `link` or `Device` within this `NvLink` struct instance is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Pascal or newer fully supported devices.
+    */
+    // Test written
+    #[doc(alias = "nvmlDeviceGetNvLinkRemoteDeviceType")]
+    pub fn remote_device_type(&self) -> Result<RemoteDeviceType, NvmlError> {
+        let sym = nvml_sym(
+            self.device
+                .nvml()
+                .lib
+                .nvmlDeviceGetNvLinkRemoteDeviceType
+                .as_ref(),
+        )?;
+
+        unsafe {
+            let mut device_type: nvmlIntNvLinkDeviceType_t = mem::zeroed();
+
+            nvml_try(sym(self.device.handle(), self.link, &mut device_type))?;
+
+            RemoteDeviceType::try_from(device_type)
+        }
+    }
+
     /**
     Gets the specified `ErrorCounter` value.
 
@@ -423,6 +491,32 @@ impl<'device, 'nvml: 'device> NvLink<'device, 'nvml> {
         }
     }
 
+    /**
+    Gets the NvLink utilization counter for every `Counter`, in the order given
+    by `Counter::all()`.
+
+    See `.utilization_counter()`, which this calls for each `Counter`; the same
+    advice about setting controls first applies.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the `link` or `Device` within this `NvLink` is invalid
+    * `NotSupported`, if this `Device` doesn't support this feature
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Pascal or newer fully supported devices.
+    */
+    // No-run test written
+    pub fn utilization_counters(&self) -> Result<Vec<(Counter, UtilizationCounter)>, NvmlError> {
+        Counter::all()
+            .iter()
+            .map(|counter| Ok((counter.clone(), self.utilization_counter(counter.clone())?)))
+            .collect()
+    }
+
     /**
     Freezes the specified NvLink utilization `Counter`.
 
@@ -557,6 +651,22 @@ mod test {
         test_with_link(3, &nvml, |link| link.has_capability(Capability::P2p))
     }
 
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn capabilities() {
+        let nvml = nvml();
+        test_with_link(3, &nvml, |link| {
+            let caps = link.capabilities()?;
+            assert_eq!(caps.len(), 6);
+            assert_eq!(
+                caps[&Capability::P2p],
+                link.has_capability(Capability::P2p)?
+            );
+
+            Ok(caps)
+        })
+    }
+
     #[test]
     #[ignore = "my machine does not support this call"]
     fn remote_pci_info() {
@@ -568,6 +678,13 @@ mod test {
         })
     }
 
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn remote_device_type() {
+        let nvml = nvml();
+        test_with_link(3, &nvml, |link| link.remote_device_type())
+    }
+
     #[test]
     #[ignore = "my machine does not support this call"]
     fn error_counter() {
@@ -625,6 +742,17 @@ mod test {
         link.utilization_counter(Counter::One).unwrap();
     }
 
+    // This shouldn't be called without modifying link state, so we don't want
+    // to actually run the test
+    #[allow(dead_code)]
+    fn utilization_counters() {
+        let nvml = nvml();
+        let device = device(&nvml);
+        let link = device.link_wrapper_for(0);
+
+        link.utilization_counters().unwrap();
+    }
+
     // This modifies link state, so we don't want to actually run the test
     #[allow(dead_code)]
     fn freeze_utilization_counter() {
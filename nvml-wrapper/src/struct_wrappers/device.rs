@@ -1,6 +1,8 @@
 use crate::bitmasks::device::FbcFlags;
-use crate::enum_wrappers::device::{BridgeChip, EncoderType, FbcSessionType, SampleValueType};
-use crate::enums::device::{FirmwareVersion, SampleValue, UsedGpuMemory};
+use crate::enum_wrappers::device::{EncoderType, FbcSessionType, SampleValueType};
+use crate::enums::device::{
+    BridgeChipType, FirmwareVersion, SampleValue, ThermalController, ThermalTarget, UsedGpuMemory,
+};
 use crate::error::{nvml_try, Bits, NvmlError};
 use crate::ffi::bindings::*;
 use crate::structs::device::FieldId;
@@ -9,6 +11,8 @@ use serde_derive::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
     ffi::{CStr, CString},
+    fmt,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use std::{
     convert::{TryFrom, TryInto},
@@ -72,6 +76,16 @@ impl PciInfo {
             })
         }
     }
+
+    /// The PCI function number (0 to 7), parsed from the end of `bus_id`.
+    ///
+    /// Returns `None` if `bus_id` does not end in a `.` followed by a valid
+    /// function number.
+    pub fn function(&self) -> Option<u32> {
+        let (_, function) = self.bus_id.trim().rsplit_once('.')?;
+
+        u32::from_str_radix(function, 16).ok().filter(|&f| f <= 0x7)
+    }
 }
 
 impl TryInto<nvmlPciInfo_t> for PciInfo {
@@ -154,33 +168,41 @@ impl From<nvmlBAR1Memory_t> for BAR1MemoryInfo {
     }
 }
 
+impl BAR1MemoryInfo {
+    /// Returns the fraction of memory that is in use (`used / total`), from
+    /// 0.0 to 1.0.
+    ///
+    /// Returns 0.0 if `total` is 0.
+    pub fn used_fraction(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.used as f64 / self.total as f64
+        }
+    }
+}
+
 /// Information about a bridge chip.
 // Checked against local
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct BridgeChipInfo {
     pub fw_version: FirmwareVersion,
-    pub chip_type: BridgeChip,
+    pub chip_type: BridgeChipType,
 }
 
-impl TryFrom<nvmlBridgeChipInfo_t> for BridgeChipInfo {
-    type Error = NvmlError;
-
-    /**
-    Construct `BridgeChipInfo` from the corresponding C struct.
-
-    # Errors
-
-    * `UnexpectedVariant`, for which you can read the docs for
-    */
-    fn try_from(value: nvmlBridgeChipInfo_t) -> Result<Self, Self::Error> {
-        let fw_version = FirmwareVersion::from(value.fwVersion);
-        let chip_type = BridgeChip::try_from(value.type_)?;
+impl From<nvmlBridgeChipInfo_t> for BridgeChipInfo {
+    fn from(value: nvmlBridgeChipInfo_t) -> Self {
+        Self {
+            fw_version: FirmwareVersion::from(value.fwVersion),
+            chip_type: BridgeChipType::from(value.type_),
+        }
+    }
+}
 
-        Ok(Self {
-            fw_version,
-            chip_type,
-        })
+impl fmt::Display for BridgeChipInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (firmware {})", self.chip_type, self.fw_version)
     }
 }
 
@@ -189,6 +211,9 @@ This struct stores the complete hierarchy of the bridge chip within the board.
 
 The immediate bridge is stored at index 0 of `chips_hierarchy`. The parent to
 the immediate bridge is at index 1, and so forth.
+
+The `Display` implementation lists the chips from the immediate bridge
+outwards, e.g. `PLX (firmware 0x1a2b) -> BRO4 (firmware unavailable)`.
 */
 // Checked against local
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -200,27 +225,41 @@ pub struct BridgeChipHierarchy {
     pub chip_count: u8,
 }
 
-impl TryFrom<nvmlBridgeChipHierarchy_t> for BridgeChipHierarchy {
-    type Error = NvmlError;
-
-    /**
-    Construct `BridgeChipHierarchy` from the corresponding C struct.
-
-    # Errors
-
-    * `UnexpectedVariant`, for which you can read the docs for
-    */
-    fn try_from(value: nvmlBridgeChipHierarchy_t) -> Result<Self, Self::Error> {
+impl From<nvmlBridgeChipHierarchy_t> for BridgeChipHierarchy {
+    /// Construct `BridgeChipHierarchy` from the corresponding C struct.
+    ///
+    /// Only the first `bridgeCount` entries of the C struct's array are
+    /// populated by NVML; the rest are ignored.
+    fn from(value: nvmlBridgeChipHierarchy_t) -> Self {
         let chips_hierarchy = value
             .bridgeChipInfo
             .iter()
-            .map(|bci| BridgeChipInfo::try_from(*bci))
-            .collect::<Result<_, NvmlError>>()?;
+            .take(value.bridgeCount as usize)
+            .map(|bci| BridgeChipInfo::from(*bci))
+            .collect();
 
-        Ok(Self {
+        Self {
             chips_hierarchy,
             chip_count: value.bridgeCount,
-        })
+        }
+    }
+}
+
+impl fmt::Display for BridgeChipHierarchy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.chips_hierarchy.is_empty() {
+            return f.write_str("no bridge chips");
+        }
+
+        for (i, chip) in self.chips_hierarchy.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" -> ")?;
+            }
+
+            write!(f, "{}", chip)?;
+        }
+
+        Ok(())
     }
 }
 
@@ -309,6 +348,20 @@ impl From<nvmlMemory_t> for MemoryInfo {
     }
 }
 
+impl MemoryInfo {
+    /// Returns the fraction of memory that is in use (`used / total`), from
+    /// 0.0 to 1.0.
+    ///
+    /// Returns 0.0 if `total` is 0.
+    pub fn used_fraction(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.used as f64 / self.total as f64
+        }
+    }
+}
+
 /// Utilization information for a device. Each sample period may be between 1
 /// second and 1/6 second, depending on the product being queried.
 // Checked against local
@@ -343,6 +396,20 @@ pub struct ViolationTime {
     pub violation_time: u64,
 }
 
+impl ViolationTime {
+    /// The reference timestamp as a `Duration` since the epoch of the CPU clock.
+    ///
+    /// Note that `reference_time` is in microseconds, unlike `violation_time`.
+    pub fn reference_duration(&self) -> Duration {
+        Duration::from_micros(self.reference_time)
+    }
+
+    /// The violation time as a `Duration`.
+    pub fn violation_duration(&self) -> Duration {
+        Duration::from_nanos(self.violation_time)
+    }
+}
+
 impl From<nvmlViolationTime_t> for ViolationTime {
     fn from(struct_: nvmlViolationTime_t) -> Self {
         Self {
@@ -392,6 +459,22 @@ pub struct AccountingStats {
     pub time: u64,
 }
 
+impl AccountingStats {
+    /// The amount of time during which the compute context was active as a
+    /// `Duration`.
+    pub fn time_duration(&self) -> Duration {
+        Duration::from_millis(self.time)
+    }
+
+    /// The start time of the process as a `SystemTime`.
+    ///
+    /// Note that `start_time` is in microseconds since the Unix epoch, unlike
+    /// `time`.
+    pub fn start_system_time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_micros(self.start_time)
+    }
+}
+
 impl From<nvmlAccountingStats_t> for AccountingStats {
     fn from(struct_: nvmlAccountingStats_t) -> Self {
         let not_avail_u64 = (NVML_VALUE_NOT_AVAILABLE) as u64;
@@ -493,6 +576,121 @@ impl Sample {
     }
 }
 
+/**
+Reusable storage for `Device.samples_into_buffer()`.
+
+Holds both the buffer that NVML writes raw samples into and the converted
+`Sample`s, so that polling with the same `SampleBuffer` doesn't allocate once
+it has grown large enough for the number of samples NVML returns.
+*/
+#[derive(Default)]
+pub struct SampleBuffer {
+    pub(crate) raw: Vec<nvmlSample_t>,
+    pub(crate) samples: Vec<Sample>,
+}
+
+impl SampleBuffer {
+    /// Creates an empty buffer that does not allocate until it is first used.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty buffer with room for `capacity` samples.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            raw: Vec::with_capacity(capacity),
+            samples: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// The samples written by the last call to `Device.samples_into_buffer()`.
+    pub fn samples(&self) -> &[Sample] {
+        &self.samples
+    }
+
+    /// The number of samples this buffer can hold without reallocating.
+    pub fn capacity(&self) -> usize {
+        self.raw.capacity().min(self.samples.capacity())
+    }
+}
+
+impl fmt::Debug for SampleBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SampleBuffer")
+            .field("samples", &self.samples)
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+/**
+Types that a `SampleValue` of a matching variant can be read as.
+
+Used by `Device.samples_typed()` to hand back samples with a value type that is
+known at compile time.
+*/
+pub trait FromSampleValue: Sized {
+    /// Returns the value held by `value` if it is of this type, or `None` if
+    /// it holds a different type.
+    fn from_sample_value(value: SampleValue) -> Option<Self>;
+}
+
+impl FromSampleValue for f64 {
+    fn from_sample_value(value: SampleValue) -> Option<Self> {
+        match value {
+            SampleValue::F64(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl FromSampleValue for u32 {
+    fn from_sample_value(value: SampleValue) -> Option<Self> {
+        match value {
+            SampleValue::U32(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl FromSampleValue for u64 {
+    fn from_sample_value(value: SampleValue) -> Option<Self> {
+        match value {
+            SampleValue::U64(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+impl FromSampleValue for i64 {
+    fn from_sample_value(value: SampleValue) -> Option<Self> {
+        match value {
+            SampleValue::I64(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+/// Sample info with a value type known at compile time, as returned from
+/// `Device.samples_typed()`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TypedSample<V> {
+    /// CPU timestamp in μs
+    pub timestamp: u64,
+    pub value: V,
+}
+
+impl<V: FromSampleValue> TypedSample<V> {
+    /// Converts a `Sample`, returning `None` if its value is not of type `V`.
+    pub fn from_sample(sample: Sample) -> Option<Self> {
+        Some(Self {
+            timestamp: sample.timestamp,
+            value: V::from_sample_value(sample.value)?,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ProcessUtilizationSample {
@@ -522,12 +720,26 @@ impl From<nvmlProcessUtilizationSample_t> for ProcessUtilizationSample {
     }
 }
 
-/// Struct that stores information returned from `Device.field_values_for()`.
+impl ProcessUtilizationSample {
+    /// The CPU timestamp of this sample as a `SystemTime`.
+    ///
+    /// `timestamp` is in microseconds since the Unix epoch.
+    pub fn timestamp_system_time(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_micros(self.timestamp)
+    }
+}
+
+/// Struct that stores information returned from `Device.field_values_for()`
+/// and `Device.field_values_for_scoped()`.
 // TODO: Missing a lot of derives because of the `Result`
 #[derive(Debug)]
 pub struct FieldValueSample {
     /// The field that this sample is for.
     pub field: FieldId,
+    /// The scope ID that this sample is for (e.g. the MIG instance or link).
+    ///
+    /// This is 0 for samples obtained via `Device.field_values_for()`.
+    pub scope_id: u32,
     /// This sample's CPU timestamp in μs (Unix time).
     pub timestamp: i64,
     /**
@@ -556,6 +768,7 @@ impl TryFrom<nvmlFieldValue_t> for FieldValueSample {
     fn try_from(value: nvmlFieldValue_t) -> Result<Self, Self::Error> {
         Ok(Self {
             field: FieldId(value.fieldId),
+            scope_id: value.scopeId,
             timestamp: value.timestamp,
             latency: value.latencyUsec,
             value: match nvml_try(value.nvmlReturn) {
@@ -656,14 +869,173 @@ impl TryFrom<nvmlFBCSessionInfo_t> for FbcSessionInfo {
     }
 }
 
+/// A single thermal sensor, as returned by `Device.thermal_settings()`.
+///
+/// Temperatures are in °C.
+// Checked against local
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ThermalSensor {
+    pub controller: ThermalController,
+    pub default_min_temp: i32,
+    pub default_max_temp: i32,
+    pub current_temp: i32,
+    pub target: ThermalTarget,
+}
+
+impl TryFrom<nvmlGpuThermalSettings_t__bindgen_ty_1> for ThermalSensor {
+    type Error = NvmlError;
+
+    /**
+    Construct `ThermalSensor` from the corresponding C struct.
+
+    # Errors
+
+    * `UnexpectedVariant`, for which you can read the docs for
+    */
+    fn try_from(value: nvmlGpuThermalSettings_t__bindgen_ty_1) -> Result<Self, Self::Error> {
+        Ok(Self {
+            controller: ThermalController::try_from(value.controller)?,
+            default_min_temp: value.defaultMinTemp,
+            default_max_temp: value.defaultMaxTemp,
+            current_temp: value.currentTemp,
+            target: ThermalTarget::try_from(value.target)?,
+        })
+    }
+}
+
 #[cfg(test)]
 #[allow(unused_variables, unused_imports)]
 mod tests {
+    use super::{
+        AccountingStats, BAR1MemoryInfo, BridgeChipHierarchy, BridgeChipInfo, FieldValueSample,
+        MemoryInfo, PciInfo, ProcessUtilizationSample, Sample, TypedSample, ViolationTime,
+    };
+    use crate::enum_wrappers::device::BridgeChip;
+    use crate::enums::device::{BridgeChipType, FirmwareVersion, SampleValue};
     use crate::error::*;
+    use crate::ffi::bindings::field_id::NVML_FI_DEV_MEMORY_TEMP;
     use crate::ffi::bindings::*;
+    use crate::structs::device::FieldId;
     use crate::test_utils::*;
-    use std::convert::TryInto;
+    use std::convert::{TryFrom, TryInto};
     use std::mem;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn pci_info_function() {
+        let info = |bus_id: &str| PciInfo {
+            bus: 1,
+            bus_id: bus_id.into(),
+            device: 0,
+            domain: 0,
+            pci_device_id: 0,
+            pci_sub_system_id: None,
+        };
+
+        assert_eq!(info("00000000:01:00.0").function(), Some(0));
+        assert_eq!(info("00000000:3B:00.1").function(), Some(1));
+        assert_eq!(info("0000:3b:1f.7").function(), Some(7));
+
+        assert_eq!(info("00000000:01:00").function(), None);
+        assert_eq!(info("00000000:01:00.8").function(), None);
+        assert_eq!(info("").function(), None);
+    }
+
+    #[test]
+    fn typed_sample_matching_type() {
+        let sample = Sample {
+            timestamp: 10,
+            value: SampleValue::U32(42),
+        };
+
+        assert_eq!(
+            TypedSample::<u32>::from_sample(sample),
+            Some(TypedSample {
+                timestamp: 10,
+                value: 42
+            })
+        );
+
+        let sample = Sample {
+            timestamp: 20,
+            value: SampleValue::F64(1.5),
+        };
+
+        assert_eq!(
+            TypedSample::<f64>::from_sample(sample).map(|s| s.value),
+            Some(1.5)
+        );
+    }
+
+    #[test]
+    fn typed_sample_mismatched_type() {
+        let sample = Sample {
+            timestamp: 10,
+            value: SampleValue::U32(42),
+        };
+
+        // Values aren't widened; the type has to match exactly
+        assert_eq!(TypedSample::<u64>::from_sample(sample.clone()), None);
+        assert_eq!(TypedSample::<f64>::from_sample(sample.clone()), None);
+        assert_eq!(TypedSample::<i64>::from_sample(sample), None);
+    }
+
+    #[test]
+    fn bridge_chip_hierarchy_from_c() {
+        let mut raw: nvmlBridgeChipHierarchy_t = unsafe { mem::zeroed() };
+        raw.bridgeCount = 3;
+        raw.bridgeChipInfo[0].type_ = nvmlBridgeChipType_enum_NVML_BRIDGE_CHIP_PLX;
+        raw.bridgeChipInfo[0].fwVersion = 0x1a2b;
+        raw.bridgeChipInfo[1].type_ = nvmlBridgeChipType_enum_NVML_BRIDGE_CHIP_BRO4;
+        raw.bridgeChipInfo[2].type_ = 42;
+        raw.bridgeChipInfo[2].fwVersion = 7;
+
+        let hierarchy = BridgeChipHierarchy::from(raw);
+
+        assert_eq!(hierarchy.chip_count, 3);
+        assert_eq!(
+            hierarchy.chips_hierarchy,
+            vec![
+                BridgeChipInfo {
+                    fw_version: FirmwareVersion::Version(0x1a2b),
+                    chip_type: BridgeChipType::Known(BridgeChip::PLX),
+                },
+                BridgeChipInfo {
+                    fw_version: FirmwareVersion::Unavailable,
+                    chip_type: BridgeChipType::Known(BridgeChip::BRO4),
+                },
+                BridgeChipInfo {
+                    fw_version: FirmwareVersion::Version(7),
+                    chip_type: BridgeChipType::Unknown(42),
+                },
+            ]
+        );
+        assert_eq!(
+            hierarchy.to_string(),
+            "PLX (firmware 0x1a2b) -> BRO4 (firmware unavailable) -> unknown (42) (firmware 0x7)"
+        );
+
+        let empty =
+            BridgeChipHierarchy::from(unsafe { mem::zeroed::<nvmlBridgeChipHierarchy_t>() });
+        assert!(empty.chips_hierarchy.is_empty());
+        assert_eq!(empty.to_string(), "no bridge chips");
+    }
+
+    #[test]
+    fn field_value_sample_scope_id() {
+        let mut raw: nvmlFieldValue_t = unsafe { mem::zeroed() };
+        raw.fieldId = NVML_FI_DEV_MEMORY_TEMP;
+        raw.scopeId = 3;
+        raw.valueType = nvmlValueType_enum_NVML_VALUE_TYPE_UNSIGNED_INT;
+        raw.value.uiVal = 42;
+
+        let sample = FieldValueSample::try_from(raw).expect("sample");
+
+        assert_eq!(sample.field, FieldId(NVML_FI_DEV_MEMORY_TEMP));
+        assert_eq!(sample.scope_id, 3);
+        assert!(matches!(sample.value, Ok(SampleValue::U32(42))));
+    }
 
     #[test]
     fn pci_info_from_to_c() {
@@ -693,4 +1065,106 @@ mod tests {
             Ok(())
         })
     }
+
+    #[test]
+    fn violation_time_durations() {
+        let time = ViolationTime {
+            reference_time: 1_500_000,
+            violation_time: 2_000_000_500,
+        };
+
+        assert_eq!(time.reference_duration(), Duration::from_millis(1500));
+        assert_eq!(time.violation_duration(), Duration::new(2, 500));
+    }
+
+    #[test]
+    fn accounting_stats_conversions() {
+        let raw = nvmlAccountingStats_t {
+            gpuUtilization: 50,
+            memoryUtilization: 25,
+            maxMemoryUsage: 4096,
+            time: 1_500,
+            startTime: 1_700_000_000_000_250,
+            isRunning: 0,
+            reserved: [0; 5],
+        };
+        let stats = AccountingStats::from(raw);
+
+        assert_eq!(stats.max_memory_usage, Some(4096));
+        assert_eq!(stats.time_duration(), Duration::from_millis(1500));
+        assert_eq!(
+            stats.start_system_time(),
+            UNIX_EPOCH + Duration::new(1_700_000_000, 250_000)
+        );
+    }
+
+    #[test]
+    fn process_utilization_sample_timestamp_system_time() {
+        let sample = ProcessUtilizationSample {
+            pid: 1,
+            timestamp: 1_700_000_000_000_250,
+            sm_util: 0,
+            mem_util: 0,
+            enc_util: 0,
+            dec_util: 0,
+        };
+
+        assert_eq!(
+            sample.timestamp_system_time(),
+            UNIX_EPOCH + Duration::new(1_700_000_000, 250_000)
+        );
+    }
+
+    #[test]
+    fn accounting_stats_not_available() {
+        let raw = nvmlAccountingStats_t {
+            gpuUtilization: NVML_VALUE_NOT_AVAILABLE as u32,
+            memoryUtilization: NVML_VALUE_NOT_AVAILABLE as u32,
+            maxMemoryUsage: NVML_VALUE_NOT_AVAILABLE as u64,
+            time: 0,
+            startTime: 0,
+            isRunning: 1,
+            reserved: [0; 5],
+        };
+        let stats = AccountingStats::from(raw);
+
+        assert_eq!(stats.gpu_utilization, None);
+        assert_eq!(stats.memory_utilization, None);
+        assert_eq!(stats.max_memory_usage, None);
+        assert_eq!(stats.start_system_time(), UNIX_EPOCH);
+    }
+
+    #[test]
+    fn memory_used_fraction() {
+        let info = MemoryInfo {
+            free: 768,
+            total: 1024,
+            used: 256,
+        };
+        assert_eq!(info.used_fraction(), 0.25);
+
+        let bar1 = BAR1MemoryInfo {
+            free: 0,
+            total: 512,
+            used: 512,
+        };
+        assert_eq!(bar1.used_fraction(), 1.0);
+    }
+
+    #[test]
+    fn memory_used_fraction_zero_total() {
+        let info = MemoryInfo {
+            free: 0,
+            total: 0,
+            used: 0,
+        };
+        assert_eq!(info.used_fraction(), 0.0);
+
+        let bar1 = BAR1MemoryInfo {
+            free: 0,
+            total: 0,
+            used: 0,
+        };
+        assert_eq!(bar1.used_fraction(), 0.0);
+    }
 }
@@ -2,6 +2,7 @@ pub mod device;
 pub mod event;
 pub mod nv_link;
 pub mod unit;
+pub mod vgpu;
 
 use self::device::PciInfo;
 use crate::error::NvmlError;
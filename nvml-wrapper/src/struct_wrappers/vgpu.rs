@@ -0,0 +1,448 @@
+use crate::bitmasks::vgpu::{VgpuCompatibilityLimit, VgpuVmCompatibility};
+use crate::error::NvmlError;
+use crate::ffi::bindings::*;
+#[cfg(feature = "serde")]
+use serde_derive::{Deserialize, Serialize};
+use std::{mem, os::raw::c_char, ptr, slice};
+
+/// The number of bytes of opaque data that fit inside the `opaqueData` array
+/// at the end of NVML's vGPU metadata structs; the rest follows the struct.
+const INLINE_OPAQUE_DATA_LEN: usize = 4;
+
+/**
+vGPU metadata for a vGPU instance, as returned from
+`VgpuInstance.metadata()`.
+
+NVML hands back this metadata as a struct followed by an opaque blob. This
+stores the whole thing as bytes (see `.as_bytes()`) so that it can be passed
+back to `Nvml.vgpu_compatibility()`, possibly on another host, and exposes the
+documented fields through accessors.
+
+Bytes obtained from `.as_bytes()` can be turned back into a `VgpuMetadata`
+via `TryFrom<Vec<u8>>`, which checks that they hold at least the full struct.
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "Vec<u8>", into = "Vec<u8>")
+)]
+pub struct VgpuMetadata {
+    data: Vec<u8>,
+}
+
+impl VgpuMetadata {
+    // Copies the metadata out of a buffer filled in by NVML.
+    pub(crate) fn from_buffer(buffer: &[nvmlVgpuMetadata_t]) -> Self {
+        let header = &buffer[0];
+
+        Self {
+            data: unsafe { metadata_bytes(buffer, header.opaqueDataSize) },
+        }
+    }
+
+    // An aligned copy of this metadata that can be handed to NVML.
+    pub(crate) fn to_buffer(&self) -> Vec<nvmlVgpuMetadata_t> {
+        unsafe { metadata_buffer_from_bytes(&self.data) }
+    }
+
+    fn header(&self) -> nvmlVgpuMetadata_t {
+        unsafe { ptr::read_unaligned(self.data.as_ptr() as *const nvmlVgpuMetadata_t) }
+    }
+
+    /// The raw bytes of this metadata, including the opaque data.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The version of this metadata's format.
+    pub fn version(&self) -> u32 {
+        self.header().version
+    }
+
+    /// The revision of this metadata's format.
+    pub fn revision(&self) -> u32 {
+        self.header().revision
+    }
+
+    /// Whether or not the guest driver has initialized the guest information
+    /// in this metadata (such as `.guest_driver_version()`).
+    pub fn guest_info_initialized(&self) -> bool {
+        self.header().guestInfoState
+            == nvmlVgpuGuestInfoState_enum_NVML_VGPU_INSTANCE_GUEST_INFO_STATE_INITIALIZED
+    }
+
+    /**
+    The version of the driver running in the guest VM.
+
+    # Errors
+
+    * `Utf8Error`, if the string is not valid UTF-8
+    */
+    pub fn guest_driver_version(&self) -> Result<String, NvmlError> {
+        c_char_array_to_string(&self.header().guestDriverVersion)
+    }
+
+    /**
+    The version of the driver running on the host.
+
+    # Errors
+
+    * `Utf8Error`, if the string is not valid UTF-8
+    */
+    pub fn host_driver_version(&self) -> Result<String, NvmlError> {
+        c_char_array_to_string(&self.header().hostDriverVersion)
+    }
+
+    /// Whether or not the vGPU can be live-migrated.
+    pub fn is_migration_capable(&self) -> bool {
+        self.header().vgpuVirtualizationCaps & NVML_VGPU_VIRTUALIZATION_CAP_MIGRATION_YES != 0
+    }
+
+    /// The version of the vGPU that is in use by the guest.
+    pub fn guest_vgpu_version(&self) -> u32 {
+        self.header().guestVgpuVersion
+    }
+
+    /// The opaque data that follows the documented fields.
+    pub fn opaque_data(&self) -> &[u8] {
+        &self.data[opaque_data_offset::<nvmlVgpuMetadata_t>()..]
+    }
+}
+
+/**
+vGPU metadata for a physical GPU, as returned from `Device.vgpu_metadata()`.
+
+Stored as bytes in the same way as `VgpuMetadata`.
+*/
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "Vec<u8>", into = "Vec<u8>")
+)]
+pub struct VgpuPgpuMetadata {
+    data: Vec<u8>,
+}
+
+impl VgpuPgpuMetadata {
+    // Copies the metadata out of a buffer filled in by NVML.
+    pub(crate) fn from_buffer(buffer: &[nvmlVgpuPgpuMetadata_t]) -> Self {
+        let header = &buffer[0];
+
+        Self {
+            data: unsafe { metadata_bytes(buffer, header.opaqueDataSize) },
+        }
+    }
+
+    // An aligned copy of this metadata that can be handed to NVML.
+    pub(crate) fn to_buffer(&self) -> Vec<nvmlVgpuPgpuMetadata_t> {
+        unsafe { metadata_buffer_from_bytes(&self.data) }
+    }
+
+    fn header(&self) -> nvmlVgpuPgpuMetadata_t {
+        unsafe { ptr::read_unaligned(self.data.as_ptr() as *const nvmlVgpuPgpuMetadata_t) }
+    }
+
+    /// The raw bytes of this metadata, including the opaque data.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// The version of this metadata's format.
+    pub fn version(&self) -> u32 {
+        self.header().version
+    }
+
+    /// The revision of this metadata's format.
+    pub fn revision(&self) -> u32 {
+        self.header().revision
+    }
+
+    /**
+    The version of the driver running on the host.
+
+    # Errors
+
+    * `Utf8Error`, if the string is not valid UTF-8
+    */
+    pub fn host_driver_version(&self) -> Result<String, NvmlError> {
+        c_char_array_to_string(&self.header().hostDriverVersion)
+    }
+
+    /// Whether or not vGPUs on this physical GPU can be live-migrated.
+    pub fn is_migration_capable(&self) -> bool {
+        self.header().pgpuVirtualizationCaps & NVML_VGPU_PGPU_VIRTUALIZATION_CAP_MIGRATION_YES != 0
+    }
+
+    /// The range of vGPU versions supported by the host driver, as
+    /// `(min, max)`.
+    pub fn host_supported_vgpu_range(&self) -> (u32, u32) {
+        let range = self.header().hostSupportedVgpuRange;
+
+        (range.minVersion, range.maxVersion)
+    }
+
+    /// The opaque data that follows the documented fields.
+    pub fn opaque_data(&self) -> &[u8] {
+        &self.data[opaque_data_offset::<nvmlVgpuPgpuMetadata_t>()..]
+    }
+}
+
+impl TryFrom<Vec<u8>> for VgpuMetadata {
+    type Error = NvmlError;
+
+    /**
+    Wraps metadata bytes previously obtained from `.as_bytes()`.
+
+    # Errors
+
+    * `InvalidArg`, if `data` is too short to hold NVML's metadata struct
+    */
+    fn try_from(data: Vec<u8>) -> Result<Self, Self::Error> {
+        check_metadata_len::<nvmlVgpuMetadata_t>(&data)?;
+
+        Ok(Self { data })
+    }
+}
+
+impl From<VgpuMetadata> for Vec<u8> {
+    fn from(metadata: VgpuMetadata) -> Self {
+        metadata.data
+    }
+}
+
+impl TryFrom<Vec<u8>> for VgpuPgpuMetadata {
+    type Error = NvmlError;
+
+    /**
+    Wraps metadata bytes previously obtained from `.as_bytes()`.
+
+    # Errors
+
+    * `InvalidArg`, if `data` is too short to hold NVML's metadata struct
+    */
+    fn try_from(data: Vec<u8>) -> Result<Self, Self::Error> {
+        check_metadata_len::<nvmlVgpuPgpuMetadata_t>(&data)?;
+
+        Ok(Self { data })
+    }
+}
+
+impl From<VgpuPgpuMetadata> for Vec<u8> {
+    fn from(metadata: VgpuPgpuMetadata) -> Self {
+        metadata.data
+    }
+}
+
+/// Returned from `Nvml.vgpu_compatibility()`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VgpuPgpuCompatibility {
+    /// The ways in which a VM running the vGPU can be migrated to the
+    /// physical GPU.
+    pub vm_compatibility: VgpuVmCompatibility,
+    /// What limits the compatibility, if anything.
+    pub limit: VgpuCompatibilityLimit,
+}
+
+impl From<nvmlVgpuPgpuCompatibility_t> for VgpuPgpuCompatibility {
+    fn from(struct_: nvmlVgpuPgpuCompatibility_t) -> Self {
+        Self {
+            vm_compatibility: VgpuVmCompatibility::from_bits_retain(struct_.vgpuVmCompatibility),
+            limit: VgpuCompatibilityLimit::from_bits_retain(struct_.compatibilityLimitCode),
+        }
+    }
+}
+
+// The opaque data array is the last field of both metadata structs and neither
+// has any trailing padding, so it starts this far into the struct.
+fn opaque_data_offset<T>() -> usize {
+    mem::size_of::<T>() - INLINE_OPAQUE_DATA_LEN
+}
+
+// The accessors read a whole `T` from the start of the bytes, so they must
+// hold at least that much.
+fn check_metadata_len<T>(data: &[u8]) -> Result<(), NvmlError> {
+    if data.len() < mem::size_of::<T>() {
+        return Err(NvmlError::InvalidArg);
+    }
+
+    Ok(())
+}
+
+// Copies the metadata struct at the start of `buffer` and `opaque_data_size`
+// bytes of opaque data after it, clamped to the size of `buffer`.
+unsafe fn metadata_bytes<T>(buffer: &[T], opaque_data_size: u32) -> Vec<u8> {
+    let buffer_len = mem::size_of_val(buffer);
+    let len = (opaque_data_offset::<T>() + opaque_data_size as usize)
+        .clamp(mem::size_of::<T>(), buffer_len);
+
+    slice::from_raw_parts(buffer.as_ptr() as *const u8, len).to_vec()
+}
+
+// Copies `bytes` into a zeroed buffer of `T`s that is large enough to hold
+// them.
+//
+// `T` must be a C struct for which all-zero bytes are a valid value.
+unsafe fn metadata_buffer_from_bytes<T: Copy>(bytes: &[u8]) -> Vec<T> {
+    let item_size = mem::size_of::<T>();
+    let len = ((bytes.len() + item_size - 1) / item_size).max(1);
+    let mut buffer: Vec<T> = vec![mem::zeroed(); len];
+
+    ptr::copy_nonoverlapping(bytes.as_ptr(), buffer.as_mut_ptr() as *mut u8, bytes.len());
+
+    buffer
+}
+
+fn c_char_array_to_string(array: &[c_char]) -> Result<String, NvmlError> {
+    let bytes: Vec<u8> = array
+        .iter()
+        .take_while(|&&c| c != 0)
+        .map(|&c| c as u8)
+        .collect();
+
+    Ok(std::str::from_utf8(&bytes)?.into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::mem::MaybeUninit;
+
+    fn set_c_string(array: &mut [c_char], s: &str) {
+        for (dst, src) in array.iter_mut().zip(s.bytes()) {
+            *dst = src as c_char;
+        }
+    }
+
+    #[test]
+    fn opaque_data_offsets() {
+        let vgpu = MaybeUninit::<nvmlVgpuMetadata_t>::uninit();
+        let pgpu = MaybeUninit::<nvmlVgpuPgpuMetadata_t>::uninit();
+
+        unsafe {
+            let base = vgpu.as_ptr();
+            let field = ptr::addr_of!((*base).opaqueData);
+            assert_eq!(
+                field as usize - base as usize,
+                opaque_data_offset::<nvmlVgpuMetadata_t>()
+            );
+
+            let base = pgpu.as_ptr();
+            let field = ptr::addr_of!((*base).opaqueData);
+            assert_eq!(
+                field as usize - base as usize,
+                opaque_data_offset::<nvmlVgpuPgpuMetadata_t>()
+            );
+        }
+    }
+
+    #[test]
+    fn vgpu_metadata_from_buffer() {
+        let opaque: Vec<u8> = (1..=10).collect();
+
+        let mut buffer: Vec<nvmlVgpuMetadata_t> = vec![unsafe { mem::zeroed() }; 2];
+        buffer[0].version = 3;
+        buffer[0].revision = 1;
+        buffer[0].guestInfoState =
+            nvmlVgpuGuestInfoState_enum_NVML_VGPU_INSTANCE_GUEST_INFO_STATE_INITIALIZED;
+        set_c_string(&mut buffer[0].guestDriverVersion, "535.54");
+        set_c_string(&mut buffer[0].hostDriverVersion, "535.54.03");
+        buffer[0].vgpuVirtualizationCaps = NVML_VGPU_VIRTUALIZATION_CAP_MIGRATION_YES;
+        buffer[0].guestVgpuVersion = 7;
+        buffer[0].opaqueDataSize = opaque.len() as u32;
+
+        unsafe {
+            let dst =
+                (buffer.as_mut_ptr() as *mut u8).add(opaque_data_offset::<nvmlVgpuMetadata_t>());
+            ptr::copy_nonoverlapping(opaque.as_ptr(), dst, opaque.len());
+        }
+
+        let metadata = VgpuMetadata::from_buffer(&buffer);
+
+        assert_eq!(metadata.version(), 3);
+        assert_eq!(metadata.revision(), 1);
+        assert!(metadata.guest_info_initialized());
+        assert_eq!(metadata.guest_driver_version().unwrap(), "535.54");
+        assert_eq!(metadata.host_driver_version().unwrap(), "535.54.03");
+        assert!(metadata.is_migration_capable());
+        assert_eq!(metadata.guest_vgpu_version(), 7);
+        assert_eq!(metadata.opaque_data(), &opaque[..]);
+
+        // Round-trips through the buffer that is handed back to NVML
+        let round_tripped = VgpuMetadata::from_buffer(&metadata.to_buffer());
+        assert_eq!(round_tripped, metadata);
+    }
+
+    #[test]
+    fn vgpu_pgpu_metadata_from_buffer() {
+        let mut buffer: Vec<nvmlVgpuPgpuMetadata_t> = vec![unsafe { mem::zeroed() }; 1];
+        buffer[0].version = 2;
+        set_c_string(&mut buffer[0].hostDriverVersion, "550.90.07");
+        buffer[0].hostSupportedVgpuRange = nvmlVgpuVersion_t {
+            minVersion: 0x10001,
+            maxVersion: 0x12000,
+        };
+        // Claims more opaque data than the buffer holds
+        buffer[0].opaqueDataSize = 1000;
+
+        let metadata = VgpuPgpuMetadata::from_buffer(&buffer);
+
+        assert_eq!(metadata.version(), 2);
+        assert_eq!(metadata.host_driver_version().unwrap(), "550.90.07");
+        assert!(!metadata.is_migration_capable());
+        assert_eq!(metadata.host_supported_vgpu_range(), (0x10001, 0x12000));
+        assert_eq!(
+            metadata.as_bytes().len(),
+            mem::size_of::<nvmlVgpuPgpuMetadata_t>()
+        );
+        assert_eq!(metadata.opaque_data().len(), INLINE_OPAQUE_DATA_LEN);
+    }
+
+    #[test]
+    fn metadata_from_bytes() {
+        let buffer: Vec<nvmlVgpuMetadata_t> = vec![unsafe { mem::zeroed() }; 1];
+        let metadata = VgpuMetadata::from_buffer(&buffer);
+
+        assert_eq!(
+            VgpuMetadata::try_from(metadata.as_bytes().to_vec()).unwrap(),
+            metadata
+        );
+        assert!(matches!(
+            VgpuMetadata::try_from(vec![0; 8]),
+            Err(NvmlError::InvalidArg)
+        ));
+        assert!(matches!(
+            VgpuPgpuMetadata::try_from(vec![0; mem::size_of::<nvmlVgpuPgpuMetadata_t>() - 1]),
+            Err(NvmlError::InvalidArg)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_rejects_short_metadata() {
+        use serde_test::{assert_de_tokens_error, Token};
+
+        assert_de_tokens_error::<VgpuMetadata>(
+            &[Token::Seq { len: Some(1) }, Token::U8(0), Token::SeqEnd],
+            "a supplied argument was invalid",
+        );
+    }
+
+    #[test]
+    fn compatibility_from_c() {
+        let compatibility = VgpuPgpuCompatibility::from(nvmlVgpuPgpuCompatibility_t {
+            vgpuVmCompatibility: nvmlVgpuVmCompatibility_enum_NVML_VGPU_VM_COMPATIBILITY_COLD
+                | nvmlVgpuVmCompatibility_enum_NVML_VGPU_VM_COMPATIBILITY_LIVE,
+            compatibilityLimitCode:
+                nvmlVgpuPgpuCompatibilityLimitCode_enum_NVML_VGPU_COMPATIBILITY_LIMIT_GUEST_DRIVER,
+        });
+
+        assert_eq!(
+            compatibility.vm_compatibility,
+            VgpuVmCompatibility::COLD | VgpuVmCompatibility::LIVE
+        );
+        assert_eq!(compatibility.limit, VgpuCompatibilityLimit::GUEST_DRIVER);
+    }
+}
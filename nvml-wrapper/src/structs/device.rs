@@ -1,8 +1,9 @@
-#[cfg(target_os = "windows")]
-use crate::enum_wrappers::device::DriverModel;
-use crate::enum_wrappers::device::OperationMode;
+use crate::bitmasks::device::ThrottleReasons;
+use crate::enum_wrappers::device::{DriverModel, OperationMode, PerformanceState};
+use crate::struct_wrappers::device::Utilization;
 #[cfg(feature = "serde")]
 use serde_derive::{Deserialize, Serialize};
+use std::{fmt, time::Duration};
 
 /// Returned from `Device.auto_boosted_clocks_enabled()`
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
@@ -17,8 +18,9 @@ pub struct AutoBoostClocksEnabledInfo {
     pub is_enabled_default: bool,
 }
 
-/// Returned from `Device.decoder_utilization()` and
-/// `Device.encoder_utilization()`.
+/// Returned from `Device.decoder_utilization()`,
+/// `Device.encoder_utilization()`, `Device.jpeg_utilization()` and
+/// `Device.ofa_utilization()`.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct UtilizationInfo {
@@ -27,10 +29,9 @@ pub struct UtilizationInfo {
     pub sampling_period: u32,
 }
 
-/// Returned from `Device.driver_model()`
+/// Returned from `Device.driver_model()` and `Device.driver_model_or_none()`
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[cfg(target_os = "windows")]
 pub struct DriverModelState {
     pub current: DriverModel,
     pub pending: DriverModel,
@@ -74,6 +75,13 @@ pub struct EncoderStats {
     pub average_latency: u32,
 }
 
+impl EncoderStats {
+    /// The encode latency as a `Duration`.
+    pub fn average_latency_duration(&self) -> Duration {
+        Duration::from_micros(self.average_latency.into())
+    }
+}
+
 /// Returned from `Device.cuda_compute_capability()`
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -82,6 +90,32 @@ pub struct CudaComputeCapability {
     pub minor: i32,
 }
 
+impl CudaComputeCapability {
+    /**
+    Returns the name of the GPU architecture that this compute capability
+    belongs to (e.g. 8.6 → `"Ampere"`, 8.9 → `"Ada"`, 9.0 → `"Hopper"`).
+
+    Compute capabilities that this wrapper doesn't know about map to
+    `"Unknown"`.
+    */
+    pub fn architecture_name(&self) -> &'static str {
+        match (self.major, self.minor) {
+            (1, _) => "Tesla",
+            (2, _) => "Fermi",
+            (3, _) => "Kepler",
+            (5, _) => "Maxwell",
+            (6, _) => "Pascal",
+            (7, 5) => "Turing",
+            (7, _) => "Volta",
+            (8, 9) => "Ada",
+            (8, _) => "Ampere",
+            (9, _) => "Hopper",
+            (10, _) | (12, _) => "Blackwell",
+            _ => "Unknown",
+        }
+    }
+}
+
 /// Returned from `Device.retired_pages()`
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -95,9 +129,378 @@ pub struct RetiredPage {
     pub timestamp: u64,
 }
 
+impl RetiredPage {
+    /// The hardware address formatted as `0x` followed by 16 zero-padded hex
+    /// digits, the way it appears in XID 63 messages in the driver logs.
+    pub fn address_hex(&self) -> String {
+        format!("{:#018x}", self.address)
+    }
+}
+
 /// Populate this newtype with the constants `nvml_wrapper::sys_exports::field_id::*`.
 ///
 /// Used in `FieldValue` and `Device.field_values_for()`.
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct FieldId(pub u32);
+
+/// A clock frequency, stored in MHz.
+///
+/// Returned from the `*_typed()` clock methods on `Device`, e.g.
+/// `Device.clock_info_typed()`. Displays as e.g. `1500 MHz`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Frequency(pub u32);
+
+impl Frequency {
+    /// The frequency in MHz.
+    pub fn mhz(&self) -> u32 {
+        self.0
+    }
+
+    /// The frequency in Hz.
+    pub fn hz(&self) -> u64 {
+        u64::from(self.0) * 1_000_000
+    }
+}
+
+impl From<u32> for Frequency {
+    fn from(mhz: u32) -> Self {
+        Self(mhz)
+    }
+}
+
+impl fmt::Display for Frequency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} MHz", self.0)
+    }
+}
+
+/// Returned from `Device.capability_report()`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CapabilityReport {
+    /// One entry per query, in the order the queries were made.
+    pub entries: Vec<CapabilityEntry>,
+}
+
+impl CapabilityReport {
+    /// Returns the entry for the `Device` method with the given name, if that
+    /// method was queried.
+    pub fn get(&self, method: &str) -> Option<&CapabilityEntry> {
+        self.entries.iter().find(|e| e.method == method)
+    }
+
+    /// Returns the names of the `Device` methods that succeeded.
+    pub fn supported(&self) -> impl Iterator<Item = &str> {
+        self.entries
+            .iter()
+            .filter(|e| e.result.is_ok())
+            .map(|e| e.method.as_str())
+    }
+}
+
+/// The outcome of a single query in a `CapabilityReport`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CapabilityEntry {
+    /// The name of the `Device` method that was called.
+    pub method: String,
+    /// `Ok` if the call succeeded, otherwise the name of the `NvmlError`
+    /// variant that was returned (e.g. `"NotSupported"`), without any data
+    /// that it carries.
+    pub result: Result<(), String>,
+}
+
+/// Returned from `Device.pcie_replay_and_errors()`
+///
+/// Counters that this `Device` does not report are `None`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PcieErrorSummary {
+    /// The PCIe replay counter, as returned by `Device.pcie_replay_counter()`.
+    pub replay_count: u32,
+    /// The number of times the PCIe replay counter has rolled over.
+    pub replay_rollover_count: Option<u64>,
+    /// The number of correctable PCIe errors.
+    pub correctable_errors: Option<u64>,
+    /// The number of non-fatal uncorrectable PCIe errors.
+    pub non_fatal_errors: Option<u64>,
+    /// The number of fatal uncorrectable PCIe errors.
+    pub fatal_errors: Option<u64>,
+    /// The number of PCIe unsupported request errors.
+    pub unsupported_requests: Option<u64>,
+}
+
+/// Returned from `Device.power_scaling_info()`
+///
+/// All values are in milliwatts. Values that this `Device` does not report are
+/// `None`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PowerScalingInfo {
+    /// The instantaneous power draw.
+    pub instant: Option<u32>,
+    /// The power draw averaged over a short window.
+    pub average: Option<u32>,
+    /// The minimum power limit that can be set.
+    pub min_limit: Option<u32>,
+    /// The maximum power limit that can be set.
+    pub max_limit: Option<u32>,
+    /// The power limit currently in effect.
+    pub current_limit: Option<u32>,
+}
+
+/// Returned from `Device.all_engine_utilization()`
+///
+/// Engines that the `Device` does not report on are `None`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct EngineUtilization {
+    pub encoder: Option<UtilizationInfo>,
+    pub decoder: Option<UtilizationInfo>,
+    pub jpeg: Option<UtilizationInfo>,
+    pub ofa: Option<UtilizationInfo>,
+}
+
+/// Returned from `Device.clock_table()`
+///
+/// All values are in MHz. Clocks that the `Device` does not report are `None`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ClockTable {
+    /// Current actual clock value.
+    pub current: Option<u32>,
+    /// Target application clock.
+    pub target_app_clock: Option<u32>,
+    /// Default application clock target.
+    pub default_app_clock: Option<u32>,
+    /// OEM-defined maximum clock rate.
+    pub customer_max_boost: Option<u32>,
+    /// Maximum clock rate, as returned by `Device.max_clock_info()`.
+    pub max: Option<u32>,
+}
+
+/// Returned from `Device.display_status()`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DisplayStatus {
+    /// Whether a display is initialized on the `Device`.
+    pub is_active: bool,
+    /// Whether a physical display is connected to any of the `Device`'s
+    /// connectors.
+    pub is_connected: bool,
+}
+
+/// Returned from `Device.pending_retired_pages()`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PendingRetiredPages {
+    /// Whether any pages are pending retirement due to single bit ECC errors.
+    pub single_bit: bool,
+    /// Whether any pages are pending retirement due to double bit ECC errors.
+    pub double_bit: bool,
+}
+
+/// Returned from `Device.temperatures()`
+///
+/// All values are in °C. Sensors that the `Device` does not have are `None`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Temperatures {
+    /// The GPU die temperature.
+    pub gpu: Option<u32>,
+    /// The memory temperature.
+    pub memory: Option<u32>,
+}
+
+/// Returned from `Device.performance_report()`
+///
+/// A snapshot of the values a monitoring agent typically records on every
+/// tick. Values that the `Device` does not support reporting are `None`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PerformanceReport {
+    /// The current graphics clock speed, in MHz.
+    pub graphics_clock: Option<u32>,
+    /// The current SM clock speed, in MHz.
+    pub sm_clock: Option<u32>,
+    /// The current memory clock speed, in MHz.
+    pub memory_clock: Option<u32>,
+    /// The current performance state.
+    pub performance_state: Option<PerformanceState>,
+    /// The current GPU and memory utilization rates.
+    pub utilization: Option<Utilization>,
+    /// The current GPU die temperature, in °C.
+    pub temperature: Option<u32>,
+    /// The current power usage, in milliwatts.
+    pub power_usage: Option<u32>,
+    /// The power limit currently being enforced, in milliwatts.
+    pub power_limit: Option<u32>,
+    /// The reasons the clocks are currently being held back, if any.
+    pub throttle_reasons: Option<ThrottleReasons>,
+}
+
+/// Returned from `Device.full_utilization()`
+///
+/// Media engine utilization that the `Device` does not support reporting is
+/// `None`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FullUtilization {
+    /// Percent of time over the past sample period during which one or more
+    /// kernels was executing on the GPU.
+    pub gpu: u32,
+    /// Percent of time over the past sample period during which global (device)
+    /// memory was being read or written to.
+    pub memory: u32,
+    /// The current encoder utilization and its sampling period.
+    pub encoder: Option<UtilizationInfo>,
+    /// The current decoder utilization and its sampling period.
+    pub decoder: Option<UtilizationInfo>,
+}
+
+/// Returned from `Device.temperature_thresholds()`
+///
+/// All values are in °C. Thresholds that the `Device` does not report are
+/// `None`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TemperatureThresholds {
+    /// Temperature at which the GPU will shut down for hardware protection.
+    pub shutdown: Option<u32>,
+    /// Temperature at which the GPU will begin hardware throttling.
+    pub slowdown: Option<u32>,
+    /// Memory temperature at which the GPU will begin software slowdown.
+    pub memory_max: Option<u32>,
+    /// GPU temperature at which the GPU can be throttled below the base clock.
+    pub gpu_max: Option<u32>,
+    /// Minimum GPU temperature that can be set as the acoustic threshold.
+    pub acoustic_min: Option<u32>,
+    /// GPU temperature that is currently set as the acoustic threshold.
+    pub acoustic_current: Option<u32>,
+    /// Maximum GPU temperature that can be set as the acoustic threshold.
+    pub acoustic_max: Option<u32>,
+}
+
+/// Returned from `Device.version_info()`
+///
+/// Versions that the `Device` cannot report are `None`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VersionInfo {
+    /// The version of the system's graphics driver.
+    pub driver: String,
+    /// The VBIOS version.
+    pub vbios: Option<String>,
+    /// The global infoROM image version.
+    pub info_rom_image: Option<String>,
+    /// The version of the OEM infoROM object.
+    pub info_rom_oem: Option<String>,
+    /// The version of the ECC infoROM object.
+    pub info_rom_ecc: Option<String>,
+    /// The version of the power management infoROM object.
+    pub info_rom_power: Option<String>,
+}
+
+/// Returned from `Device.inforom_health()`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InforomHealth {
+    /// Whether or not the infoROM's checksum verified successfully.
+    ///
+    /// `false` means the infoROM is corrupted.
+    pub valid: bool,
+    /// The checksum of the config stored in the infoROM.
+    ///
+    /// `None` if the checksum couldn't be retrieved due to infoROM corruption
+    /// or if the `Device` does not support reporting it.
+    pub checksum: Option<u32>,
+}
+
+/// Returned from `Device.detailed_ecc_errors_modern()`
+///
+/// Locations that the `Device` does not report a count for are `None`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DetailedEccErrors {
+    pub l1_cache: Option<u64>,
+    pub l2_cache: Option<u64>,
+    pub device_memory: Option<u64>,
+    pub register_file: Option<u64>,
+    pub texture_memory: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CudaComputeCapability, EncoderStats, Frequency, RetiredPage};
+    use std::time::Duration;
+
+    fn arch(major: i32, minor: i32) -> &'static str {
+        CudaComputeCapability { major, minor }.architecture_name()
+    }
+
+    #[test]
+    fn architecture_name_known() {
+        assert_eq!(arch(3, 5), "Kepler");
+        assert_eq!(arch(6, 1), "Pascal");
+        assert_eq!(arch(7, 0), "Volta");
+        assert_eq!(arch(7, 2), "Volta");
+        assert_eq!(arch(7, 5), "Turing");
+        assert_eq!(arch(8, 0), "Ampere");
+        assert_eq!(arch(8, 6), "Ampere");
+        assert_eq!(arch(8, 9), "Ada");
+        assert_eq!(arch(9, 0), "Hopper");
+        assert_eq!(arch(10, 0), "Blackwell");
+    }
+
+    #[test]
+    fn architecture_name_unknown() {
+        assert_eq!(arch(4, 0), "Unknown");
+        assert_eq!(arch(42, 0), "Unknown");
+    }
+
+    #[test]
+    fn encoder_stats_average_latency_duration() {
+        let stats = EncoderStats {
+            session_count: 2,
+            average_fps: 60,
+            average_latency: 1_500,
+        };
+
+        assert_eq!(
+            stats.average_latency_duration(),
+            Duration::from_micros(1_500)
+        );
+        assert_eq!(
+            stats.average_latency_duration(),
+            Duration::from_nanos(1_500_000)
+        );
+    }
+
+    #[test]
+    fn retired_page_address_hex() {
+        let page = |address| RetiredPage {
+            address,
+            timestamp: 0,
+        };
+
+        assert_eq!(page(0x3c3f1c4c0).address_hex(), "0x00000003c3f1c4c0");
+        assert_eq!(page(0).address_hex(), "0x0000000000000000");
+        assert_eq!(page(u64::MAX).address_hex(), "0xffffffffffffffff");
+    }
+
+    #[test]
+    fn frequency_conversions() {
+        let freq = Frequency::from(1530);
+
+        assert_eq!(freq.mhz(), 1530);
+        assert_eq!(freq.hz(), 1_530_000_000);
+        assert_eq!(freq.to_string(), "1530 MHz");
+
+        // Doesn't overflow for the largest possible value
+        assert_eq!(Frequency(u32::MAX).hz(), u64::from(u32::MAX) * 1_000_000);
+        assert_eq!(Frequency(0).hz(), 0);
+        assert!(Frequency(300) < Frequency(1530));
+    }
+}
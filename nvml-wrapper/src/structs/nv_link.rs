@@ -10,3 +10,27 @@ pub struct UtilizationCounter {
     /// Send counter value
     pub send: u64,
 }
+
+/// Returned by `Device.nvlink_summary()`
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NvLinkSummary {
+    /// The number of valid links on the `Device`
+    pub link_count: u32,
+    /// The number of valid links that are active
+    pub active_count: u32,
+    /// The NvLink version shared by every active link
+    ///
+    /// `None` if no links are active or if the active links report different
+    /// versions.
+    pub version: Option<u32>,
+    /// Data link transmit replay errors, summed across all valid links
+    pub dl_replay_errors: u64,
+    /// Data link transmit recovery errors, summed across all valid links
+    pub dl_recovery_errors: u64,
+    /// Data link receive flow control digit CRC errors, summed across all
+    /// valid links
+    pub dl_crc_flit_errors: u64,
+    /// Data link receive data CRC errors, summed across all valid links
+    pub dl_crc_data_errors: u64,
+}
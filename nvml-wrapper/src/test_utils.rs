@@ -6,6 +6,7 @@ use crate::Unit;
 use crate::bitmasks::{device::*, event::*};
 
 use crate::enum_wrappers::device::*;
+use crate::enum_wrappers::nv_link::{Capability, RemoteDeviceType};
 use crate::enums::device::BusType;
 use crate::enums::device::DeviceArchitecture;
 use crate::enums::device::PcieLinkMaxSpeed;
@@ -13,17 +14,16 @@ use crate::enums::device::PowerSource;
 use crate::enums::unit::*;
 use crate::error::NvmlError;
 use crate::event::EventSet;
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 use crate::struct_wrappers::nv_link::*;
 use crate::struct_wrappers::{device::*, event::*, unit::*, *};
 
+use crate::struct_wrappers::vgpu::VgpuPgpuCompatibility;
 use crate::structs::device::*;
 use crate::structs::nv_link::*;
 
-#[cfg(target_os = "windows")]
-use crate::structs::device::DriverModelState;
-
 pub trait ShouldPrint: Debug {
     fn should_print(&self) -> bool {
         true
@@ -59,6 +59,7 @@ impl ShouldPrint for u32 {}
 impl ShouldPrint for i32 {}
 impl ShouldPrint for (u32, u32) {}
 impl ShouldPrint for u64 {}
+impl ShouldPrint for f64 {}
 impl ShouldPrint for String {}
 impl ShouldPrint for Brand {}
 impl ShouldPrint for [i8; 16] {}
@@ -66,9 +67,15 @@ impl ShouldPrint for Vec<ProcessInfo> {}
 impl ShouldPrint for Vec<ProcessUtilizationSample> {}
 impl<'nvml> ShouldPrint for Vec<Device<'nvml>> {}
 impl ShouldPrint for Vec<u32> {}
+impl ShouldPrint for Vec<bool> {}
 impl ShouldPrint for Vec<u64> {}
+impl ShouldPrint for Vec<String> {}
 impl ShouldPrint for Vec<Sample> {}
 impl ShouldPrint for Vec<Result<FieldValueSample, NvmlError>> {}
+impl ShouldPrint for HashMap<Capability, bool> {}
+impl ShouldPrint for HashMap<EncoderType, u32> {}
+impl ShouldPrint for RemoteDeviceType {}
+impl ShouldPrint for Vec<(u32, PciInfo, RemoteDeviceType)> {}
 impl ShouldPrint for Vec<HwbcEntry> {}
 impl ShouldPrint for Utilization {}
 impl ShouldPrint for EncoderStats {}
@@ -80,6 +87,15 @@ impl ShouldPrint for BAR1MemoryInfo {}
 impl ShouldPrint for BridgeChipHierarchy {}
 impl ShouldPrint for ComputeMode {}
 impl ShouldPrint for UtilizationInfo {}
+impl ShouldPrint for Option<UtilizationInfo> {}
+impl ShouldPrint for Option<i32> {}
+impl ShouldPrint for Option<u32> {}
+impl ShouldPrint for Vec<VgpuPgpuCompatibility> {}
+impl ShouldPrint for Option<InforomHealth> {}
+impl ShouldPrint for PerformanceReport {}
+impl ShouldPrint for FullUtilization {}
+impl ShouldPrint for PowerScalingInfo {}
+impl ShouldPrint for TemperatureThresholds {}
 impl ShouldPrint for EccModeState {}
 impl ShouldPrint for OperationModeState {}
 impl ShouldPrint for InfoRom {}
@@ -90,6 +106,7 @@ impl ShouldPrint for PciInfo {}
 impl ShouldPrint for PerformanceState {}
 impl ShouldPrint for PowerManagementConstraints {}
 impl ShouldPrint for ThrottleReasons {}
+impl ShouldPrint for ClocksEventReasons {}
 impl ShouldPrint for ViolationTime {}
 impl ShouldPrint for AccountingStats {}
 impl ShouldPrint for EventTypes {}
@@ -104,9 +121,21 @@ impl ShouldPrint for BusType {}
 impl ShouldPrint for PowerSource {}
 impl ShouldPrint for DeviceArchitecture {}
 impl ShouldPrint for PcieLinkMaxSpeed {}
+impl ShouldPrint for CapabilityReport {}
+impl ShouldPrint for PcieErrorSummary {}
+impl ShouldPrint for EngineUtilization {}
+impl ShouldPrint for Vec<ThermalSensor> {}
+impl ShouldPrint for ClockTable {}
+impl ShouldPrint for DisplayStatus {}
+impl ShouldPrint for NvLinkSummary {}
+impl ShouldPrint for Temperatures {}
+impl ShouldPrint for PendingRetiredPages {}
+impl ShouldPrint for VersionInfo {}
+impl ShouldPrint for Frequency {}
+impl ShouldPrint for DetailedEccErrors {}
 
-#[cfg(target_os = "windows")]
 impl ShouldPrint for DriverModelState {}
+impl ShouldPrint for Option<DriverModelState> {}
 
 pub fn nvml() -> Nvml {
     Nvml::init().expect("initialized library")
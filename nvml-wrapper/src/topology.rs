@@ -0,0 +1,141 @@
+use crate::enum_wrappers::device::TopologyLevel;
+use crate::error::NvmlError;
+use crate::Device;
+
+/**
+The GPU-to-GPU topology of a set of `Device`s.
+
+Obtain this via `Nvml.topology()` or build one for a subset of devices with
+`Topology::new()`. The common ancestor of every pair of devices is looked up
+once when a `Topology` is built; queries against it afterwards don't call into
+NVML. Hold on to it if you need to query the topology repeatedly, and rebuild
+it if the set of devices changes.
+
+The functionality in this module is only available on Linux platforms; NVML
+does not support topology queries on any other platform.
+*/
+#[derive(Debug)]
+pub struct Topology<'nvml> {
+    devices: Vec<Device<'nvml>>,
+    // `levels[i][j]` is the common ancestor of devices `i` and `j`; the
+    // diagonal is `None`
+    levels: Vec<Vec<Option<TopologyLevel>>>,
+}
+
+impl<'nvml> Topology<'nvml> {
+    /**
+    Builds the topology of the given `Device`s by looking up the common
+    ancestor of every pair of them.
+
+    # Errors
+
+    * `InvalidArg`, if a `Device` is invalid
+    * `NotSupported`, if a `Device` or the OS does not support this feature
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, an error has occurred in the underlying topology discovery
+    */
+    pub fn new(devices: Vec<Device<'nvml>>) -> Result<Self, NvmlError> {
+        let count = devices.len();
+        let mut levels = vec![vec![None; count]; count];
+
+        for i in 0..count {
+            for j in (i + 1)..count {
+                // `topology_common_ancestor()` takes the other device by value
+                let level = devices[i].topology_common_ancestor(devices[j].duplicate())?;
+
+                levels[i][j] = Some(level.clone());
+                levels[j][i] = Some(level);
+            }
+        }
+
+        Ok(Self { devices, levels })
+    }
+
+    /// The `Device`s in this topology, in the order they were passed in.
+    pub fn devices(&self) -> &[Device<'nvml>] {
+        &self.devices
+    }
+
+    /// Gets the common ancestor of the `Device`s at indices `a` and `b` in
+    /// `.devices()`.
+    ///
+    /// Returns `None` if `a == b` or either index is out of range.
+    pub fn level(&self, a: usize, b: usize) -> Option<&TopologyLevel> {
+        self.levels.get(a)?.get(b)?.as_ref()
+    }
+
+    /**
+    Gets the `Device`s that are connected to `device` at `level` or closer,
+    nearest first.
+
+    This is the cached equivalent of `Device.topology_nearest_gpus()`. Returns
+    an empty `Vec` if `device` is not part of this topology.
+    */
+    pub fn nearest(&self, device: &Device<'nvml>, level: TopologyLevel) -> Vec<&Device<'nvml>> {
+        match self.devices.iter().position(|d| d == device) {
+            Some(index) => nearest_indices(&self.levels[index], &level)
+                .into_iter()
+                .map(|i| &self.devices[i])
+                .collect(),
+            None => vec![],
+        }
+    }
+}
+
+// Returns the indices of the entries in `row` at `level` or closer, nearest
+// first.
+fn nearest_indices(row: &[Option<TopologyLevel>], level: &TopologyLevel) -> Vec<usize> {
+    let mut indices: Vec<(usize, u32)> = row
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| l.as_ref().map(|l| (i, l.as_c())))
+        .filter(|&(_, l)| l <= level.as_c())
+        .collect();
+
+    // Stable, so devices at the same level stay in enumeration order
+    indices.sort_by_key(|&(_, l)| l);
+    indices.into_iter().map(|(i, _)| i).collect()
+}
+
+#[cfg(test)]
+#[deny(unused_mut)]
+mod test {
+    use super::{nearest_indices, Topology};
+    use crate::enum_wrappers::device::TopologyLevel;
+    use crate::test_utils::*;
+
+    #[test]
+    fn nearest_indices_filters_and_sorts() {
+        let row = [
+            Some(TopologyLevel::System),
+            None,
+            Some(TopologyLevel::Single),
+            Some(TopologyLevel::HostBridge),
+            Some(TopologyLevel::Internal),
+            Some(TopologyLevel::Single),
+        ];
+
+        assert_eq!(nearest_indices(&row, &TopologyLevel::Internal), vec![4]);
+        assert_eq!(
+            nearest_indices(&row, &TopologyLevel::Multiple),
+            vec![4, 2, 5]
+        );
+        assert_eq!(
+            nearest_indices(&row, &TopologyLevel::System),
+            vec![4, 2, 5, 3, 0]
+        );
+        assert!(nearest_indices(&[None], &TopologyLevel::System).is_empty());
+    }
+
+    #[test]
+    fn single_device() {
+        let nvml = nvml();
+        let topology = Topology::new(vec![device(&nvml)]).expect("topology");
+
+        assert_eq!(topology.devices().len(), 1);
+        assert_eq!(topology.level(0, 0), None);
+        assert!(topology
+            .nearest(&topology.devices()[0], TopologyLevel::System)
+            .is_empty());
+    }
+}
@@ -3,7 +3,7 @@ use crate::enum_wrappers::unit::LedColor;
 use crate::enums::unit::{LedState, TemperatureReading};
 use crate::error::{nvml_sym, nvml_try, NvmlError};
 use crate::ffi::bindings::*;
-use crate::struct_wrappers::unit::{FansInfo, PsuInfo, UnitInfo};
+use crate::struct_wrappers::unit::{FanInfo, FansInfo, PsuInfo, UnitInfo};
 use crate::Nvml;
 use static_assertions::assert_impl_all;
 use std::mem;
@@ -122,6 +122,49 @@ impl<'nvml> Unit<'nvml> {
         }
     }
 
+    /**
+    Gets the GPU device at the given index within the set of devices that are
+    attached to this `Unit`.
+
+    Valid indices are less than `.device_count()`. The returned `Device` is
+    bound to the `Nvml` instance this `Unit` came from rather than to this
+    `Unit`.
+
+    **I do not have the hardware to test this call. Verify for yourself that it
+    works before you use it**.
+
+    # Errors
+
+    * `DeviceIndexOutOfRange`, if `index` is not less than `.device_count()`
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the unit is invalid
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    For S-class products.
+    */
+    // Tested
+    pub fn device_by_index(&self, index: u32) -> Result<Device<'nvml>, NvmlError> {
+        let sym = nvml_sym(self.nvml.lib.nvmlUnitGetDevices.as_ref())?;
+
+        unsafe {
+            let mut count: c_uint = self.device_count()?;
+            if index >= count {
+                return Err(NvmlError::DeviceIndexOutOfRange { index, count });
+            }
+
+            let mut devices: Vec<nvmlDevice_t> = vec![mem::zeroed(); count as usize];
+            nvml_try(sym(self.unit, &mut count, devices.as_mut_ptr()))?;
+
+            match devices.get(index as usize) {
+                // `count` may have shrunk between the two calls
+                Some(&device) if index < count => Ok(Device::new(device, self.nvml)),
+                _ => Err(NvmlError::DeviceIndexOutOfRange { index, count }),
+            }
+        }
+    }
+
     /**
     Gets the count of GPU devices that are attached to this `Unit`.
 
@@ -201,6 +244,31 @@ impl<'nvml> Unit<'nvml> {
         }
     }
 
+    /**
+    Gets per-fan information (speed in RPM and state) for this `Unit`.
+
+    Unlike `.fan_info()`, the returned list only contains entries for the fans
+    that are actually present in the unit.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if the unit is invalid
+    * `NotSupported`, if this is not an S-class product
+    * `UnexpectedVariant`, for which you can read the docs for
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    For S-class products.
+    */
+    #[doc(alias = "nvmlUnitGetFanSpeedInfo")]
+    pub fn fan_speed_info(&self) -> Result<Vec<FanInfo>, NvmlError> {
+        let info = self.fan_info()?;
+
+        Ok(info.fans.into_iter().take(info.count as usize).collect())
+    }
+
     /**
     Gets the LED state associated with this `Unit`.
 
@@ -355,6 +423,7 @@ impl<'nvml> Unit<'nvml> {
 mod test {
     use crate::enum_wrappers::unit::LedColor;
     use crate::enums::unit::TemperatureReading;
+    use crate::error::NvmlError;
     use crate::test_utils::*;
 
     #[test]
@@ -365,6 +434,26 @@ mod test {
         unit.devices().expect("devices");
     }
 
+    #[test]
+    fn device_by_index() {
+        let nvml = nvml();
+
+        // Systems without S-class hardware have no units, which is fine
+        for i in 0..nvml.unit_count().expect("unit count") {
+            let unit = nvml.unit_by_index(i).expect("unit");
+            let count = unit.device_count().expect("device count");
+
+            for j in 0..count {
+                unit.device_by_index(j).expect("device");
+            }
+
+            match unit.device_by_index(count) {
+                Err(NvmlError::DeviceIndexOutOfRange { .. }) => (),
+                other => panic!("expected `DeviceIndexOutOfRange`, got {:?}", other),
+            }
+        }
+    }
+
     #[test]
     #[ignore = "my machine does not support this call"]
     fn fan_info() {
@@ -372,6 +461,18 @@ mod test {
         test_with_unit(3, &nvml, |unit| unit.fan_info())
     }
 
+    #[test]
+    fn fan_speed_info() {
+        let nvml = nvml();
+
+        match nvml.unit_by_index(0).and_then(|unit| unit.fan_speed_info()) {
+            Ok(fans) => assert!(fans.len() <= 24),
+            // No S-class unit is present on this machine
+            Err(NvmlError::InvalidArg) | Err(NvmlError::NotSupported) => (),
+            Err(e) => panic!("unexpected error: {:?}", e),
+        }
+    }
+
     #[test]
     #[ignore = "my machine does not support this call"]
     fn led_state() {
@@ -0,0 +1,343 @@
+use crate::error::{nvml_sym, nvml_try, NvmlError};
+use crate::ffi::bindings::*;
+use crate::struct_wrappers::vgpu::VgpuMetadata;
+use crate::Device;
+
+use std::{
+    ffi::CStr,
+    mem,
+    os::raw::{c_uint, c_ulonglong},
+};
+
+use static_assertions::assert_impl_all;
+
+/**
+Struct that represents a vGPU type supported by a `Device`.
+
+Obtain this via `Device.supported_vgpus()` or `Device.creatable_vgpus()`.
+
+Lifetimes are used to enforce that each `VgpuTypeId` instance cannot be used
+after the `Device` instance it was obtained from is dropped:
+
+```compile_fail
+use nvml_wrapper::Nvml;
+# use nvml_wrapper::error::*;
+
+# fn main() -> Result<(), NvmlError> {
+let nvml = Nvml::init()?;
+let device = nvml.device_by_index(0)?;
+let vgpu_types = device.supported_vgpus()?;
+
+drop(device);
+
+// This won't compile
+vgpu_types[0].name()?;
+# Ok(())
+# }
+```
+
+Note that I cannot test any `VgpuTypeId` methods myself as I do not have access
+to a vGPU-capable host. **Test the functionality in this module before you use
+it**.
+*/
+#[derive(Debug)]
+pub struct VgpuTypeId<'device, 'nvml: 'device> {
+    pub(crate) device: &'device Device<'nvml>,
+    pub(crate) id: nvmlVgpuTypeId_t,
+}
+
+assert_impl_all!(VgpuTypeId: Send, Sync);
+
+impl<'device, 'nvml: 'device> VgpuTypeId<'device, 'nvml> {
+    /// Obtain the `Device` reference stored within this struct.
+    pub fn device(&self) -> &Device<'nvml> {
+        self.device
+    }
+
+    /// Obtain the raw vGPU type ID stored within this struct.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /**
+    Gets the name of this vGPU type.
+
+    The name is an alphanumeric string that denotes a particular vGPU, e.g.
+    GRID M60-2Q.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this vGPU type ID is invalid
+    * `NotSupported`, if the host is not running a vGPU-capable driver
+    * `Utf8Error`, if the string obtained from the C function is not valid Utf8
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlVgpuTypeGetName")]
+    pub fn name(&self) -> Result<String, NvmlError> {
+        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuTypeGetName.as_ref())?;
+
+        unsafe {
+            let mut size: c_uint = NVML_DEVICE_NAME_BUFFER_SIZE;
+            let mut name_vec = vec![0; size as usize];
+
+            nvml_try(sym(self.id, name_vec.as_mut_ptr(), &mut size))?;
+
+            let name_raw = CStr::from_ptr(name_vec.as_ptr());
+            Ok(name_raw.to_str()?.into())
+        }
+    }
+
+    /**
+    Gets the framebuffer size of this vGPU type, in bytes.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this vGPU type ID is invalid
+    * `NotSupported`, if the host is not running a vGPU-capable driver
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlVgpuTypeGetFramebufferSize")]
+    pub fn framebuffer_size(&self) -> Result<u64, NvmlError> {
+        let sym = nvml_sym(
+            self.device
+                .nvml()
+                .lib
+                .nvmlVgpuTypeGetFramebufferSize
+                .as_ref(),
+        )?;
+
+        unsafe {
+            let mut size: c_ulonglong = mem::zeroed();
+            nvml_try(sym(self.id, &mut size))?;
+
+            Ok(size)
+        }
+    }
+
+    /**
+    Gets the maximum number of vGPU instances of this type that can be created
+    on the `Device` this struct was obtained from.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this vGPU type ID or the `Device` is invalid
+    * `NotSupported`, if the host is not running a vGPU-capable driver
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Kepler and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlVgpuTypeGetMaxInstances")]
+    pub fn max_instances(&self) -> Result<u32, NvmlError> {
+        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuTypeGetMaxInstances.as_ref())?;
+
+        unsafe {
+            let mut count: c_uint = mem::zeroed();
+            nvml_try(sym(self.device.handle(), self.id, &mut count))?;
+
+            Ok(count)
+        }
+    }
+}
+
+/**
+Struct that represents a vGPU instance running on a `Device`.
+
+Obtain this via `Device.active_vgpus()`.
+
+Lifetimes are used to enforce that each `VgpuInstance` cannot be used after the
+`Device` instance it was obtained from is dropped, in the same way as for
+`VgpuTypeId`.
+
+Note that I cannot test any `VgpuInstance` methods myself as I do not have
+access to a vGPU-capable host. **Test the functionality in this module before
+you use it**.
+*/
+#[derive(Debug)]
+pub struct VgpuInstance<'device, 'nvml: 'device> {
+    pub(crate) device: &'device Device<'nvml>,
+    pub(crate) instance: nvmlVgpuInstance_t,
+}
+
+assert_impl_all!(VgpuInstance: Send, Sync);
+
+impl<'device, 'nvml: 'device> VgpuInstance<'device, 'nvml> {
+    /// Obtain the `Device` reference stored within this struct.
+    pub fn device(&self) -> &Device<'nvml> {
+        self.device
+    }
+
+    /// Obtain the raw vGPU instance handle stored within this struct.
+    pub fn instance(&self) -> u32 {
+        self.instance
+    }
+
+    /**
+    Gets the vGPU metadata for this vGPU instance.
+
+    The metadata can be passed to `Nvml.vgpu_compatibility()` along with a
+    physical GPU's metadata to check whether this vGPU can be migrated to it.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this vGPU instance is invalid
+    * `NotSupported`, if the host is not running a vGPU-capable driver
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell and newer fully supported devices.
+    */
+    #[doc(alias = "nvmlVgpuInstanceGetMetadata")]
+    pub fn metadata(&self) -> Result<VgpuMetadata, NvmlError> {
+        let sym = nvml_sym(self.device.nvml().lib.nvmlVgpuInstanceGetMetadata.as_ref())?;
+
+        unsafe {
+            let buffer = metadata_buffer(|buf, size| sym(self.instance, buf, size))?;
+
+            Ok(VgpuMetadata::from_buffer(&buffer))
+        }
+    }
+
+    /**
+    Gets whether or not this vGPU instance can be live-migrated.
+
+    This reads the virtualization capabilities from this instance's metadata.
+    Whether the instance can migrate to a particular host also depends on that
+    host; see `Nvml.vgpu_compatibility()` to check that.
+
+    # Errors
+
+    * `Uninitialized`, if the library has not been successfully initialized
+    * `InvalidArg`, if this vGPU instance is invalid
+    * `NotSupported`, if the host is not running a vGPU-capable driver
+    * `Unknown`, on any unexpected error
+
+    # Device Support
+
+    Supports Maxwell and newer fully supported devices.
+    */
+    pub fn migration_capability(&self) -> Result<bool, NvmlError> {
+        Ok(self.metadata()?.is_migration_capable())
+    }
+}
+
+/**
+Calls `query` with a buffer for a vGPU metadata struct and the buffer's size in
+bytes, growing the buffer and retrying once if NVML reports it as too small.
+
+NVML appends opaque data past the end of the metadata struct, so the buffer can
+span more than one `T`; holding `T`s keeps it aligned for the struct at the
+start.
+
+# Safety
+
+`T` must be a C struct for which all-zero bytes are a valid value.
+*/
+pub(crate) unsafe fn metadata_buffer<T, F>(mut query: F) -> Result<Vec<T>, NvmlError>
+where
+    T: Copy,
+    F: FnMut(*mut T, *mut c_uint) -> nvmlReturn_t,
+{
+    let item_size = mem::size_of::<T>();
+    let mut buffer: Vec<T> = vec![mem::zeroed(); 1];
+    let mut size = item_size as c_uint;
+
+    match query(buffer.as_mut_ptr(), &mut size) {
+        nvmlReturn_enum_NVML_ERROR_INSUFFICIENT_SIZE => {
+            // `size` has been set to the required size in bytes
+            let len = (size as usize + item_size - 1) / item_size;
+            buffer = vec![mem::zeroed(); len.max(1)];
+            size = (buffer.len() * item_size) as c_uint;
+
+            nvml_try(query(buffer.as_mut_ptr(), &mut size))?;
+        }
+        other => nvml_try(other)?,
+    }
+
+    Ok(buffer)
+}
+
+#[cfg(test)]
+#[deny(unused_mut)]
+mod test {
+    use crate::test_utils::*;
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn name() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device
+                .supported_vgpus()?
+                .iter()
+                .map(|t| t.name())
+                .collect::<Result<Vec<_>, _>>()
+        })
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn framebuffer_size() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device
+                .supported_vgpus()?
+                .iter()
+                .map(|t| t.framebuffer_size())
+                .collect::<Result<Vec<_>, _>>()
+        })
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn migration_capability() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device
+                .active_vgpus()?
+                .iter()
+                .map(|i| i.migration_capability())
+                .collect::<Result<Vec<_>, _>>()
+        })
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn metadata() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device
+                .active_vgpus()?
+                .iter()
+                .map(|i| i.metadata().map(|m| m.version()))
+                .collect::<Result<Vec<_>, _>>()
+        })
+    }
+
+    #[test]
+    #[ignore = "my machine does not support this call"]
+    fn max_instances() {
+        let nvml = nvml();
+        test_with_device(3, &nvml, |device| {
+            device
+                .supported_vgpus()?
+                .iter()
+                .map(|t| t.max_instances())
+                .collect::<Result<Vec<_>, _>>()
+        })
+    }
+}